@@ -0,0 +1,4 @@
+fn main() {
+    // Recompile when migrations change so `sqlx::migrate!` picks them up.
+    println!("cargo:rerun-if-changed=migrations");
+}
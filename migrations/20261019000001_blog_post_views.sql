@@ -0,0 +1,9 @@
+-- Unique post views, one row per visitor hash per day.
+CREATE TABLE IF NOT EXISTS blog_post_views (
+    post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    visitor_hash TEXT NOT NULL,
+    view_date DATE NOT NULL,
+    PRIMARY KEY (post_id, visitor_hash, view_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_post_views_view_date ON blog_post_views (view_date);
@@ -0,0 +1,6 @@
+-- Posts published with a future published_at stay hidden until the scheduler releases them.
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS scheduled BOOLEAN NOT NULL DEFAULT false;
+
+UPDATE blog_posts SET scheduled = true WHERE published = true AND published_at > NOW();
+
+CREATE INDEX IF NOT EXISTS idx_blog_posts_scheduled ON blog_posts (published_at) WHERE scheduled;
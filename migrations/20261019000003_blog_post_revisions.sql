@@ -0,0 +1,18 @@
+-- Snapshot of a post taken before each update, so edits can be reviewed and undone.
+CREATE TABLE IF NOT EXISTS blog_post_revisions (
+    id SERIAL PRIMARY KEY,
+    post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    excerpt TEXT NOT NULL,
+    content TEXT NOT NULL,
+    content_markdown TEXT,
+    author TEXT,
+    meta_title TEXT,
+    meta_description TEXT,
+    meta_keywords TEXT[],
+    category_ids INTEGER[] NOT NULL DEFAULT '{}',
+    tag_ids INTEGER[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_post_revisions_post_id ON blog_post_revisions (post_id, id DESC);
@@ -0,0 +1,2 @@
+-- Preview tokens embed this version; bumping it revokes every outstanding link for the post.
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS preview_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,8 @@
+-- Slugs a post was previously published under, so old links can redirect to the current one.
+CREATE TABLE IF NOT EXISTS blog_slug_history (
+    slug TEXT PRIMARY KEY,
+    post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_slug_history_post ON blog_slug_history (post_id);
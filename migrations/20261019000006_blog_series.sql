@@ -0,0 +1,16 @@
+-- Multi-part posts. A post belongs to at most one series; `position` orders the parts.
+CREATE TABLE IF NOT EXISTS blog_series (
+    id SERIAL PRIMARY KEY,
+    title TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS blog_series_posts (
+    series_id INTEGER NOT NULL REFERENCES blog_series(id) ON DELETE CASCADE,
+    post_id INTEGER NOT NULL UNIQUE REFERENCES blog_posts(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (series_id, post_id)
+);
@@ -0,0 +1,18 @@
+-- Reader comments. New comments wait in `pending` until a moderator approves them.
+CREATE TABLE IF NOT EXISTS blog_comments (
+    id SERIAL PRIMARY KEY,
+    post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    parent_id INTEGER REFERENCES blog_comments(id) ON DELETE CASCADE,
+    author_name TEXT NOT NULL,
+    author_email TEXT NOT NULL,
+    body TEXT NOT NULL,
+    body_html TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected', 'spam')),
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_comments_post_status ON blog_comments (post_id, status);
+CREATE INDEX IF NOT EXISTS idx_blog_comments_status ON blog_comments (status, created_at);
+
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS comments_closed BOOLEAN NOT NULL DEFAULT false;
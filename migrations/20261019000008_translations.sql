@@ -0,0 +1,41 @@
+-- Per-locale overrides of translatable text. The base rows hold the default locale;
+-- `fields` maps a field name (e.g. "title") to its translated text.
+CREATE TABLE IF NOT EXISTS blog_post_translations (
+    item_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    fields JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (item_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS about_translations (
+    item_id INTEGER NOT NULL REFERENCES about(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    fields JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (item_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS skill_translations (
+    item_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    fields JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (item_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS project_translations (
+    item_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    fields JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (item_id, locale)
+);
+
+CREATE TABLE IF NOT EXISTS experience_translations (
+    item_id INTEGER NOT NULL REFERENCES experience_timeline(id) ON DELETE CASCADE,
+    locale TEXT NOT NULL,
+    fields JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMP DEFAULT NOW(),
+    PRIMARY KEY (item_id, locale)
+);
@@ -0,0 +1,8 @@
+-- Slugs a tag was previously known by (renamed or merged away), so old tag links keep working.
+CREATE TABLE IF NOT EXISTS blog_tag_slug_history (
+    slug TEXT PRIMARY KEY,
+    tag_id INTEGER NOT NULL REFERENCES blog_tags(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_tag_slug_history_tag ON blog_tag_slug_history (tag_id);
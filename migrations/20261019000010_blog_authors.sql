@@ -0,0 +1,47 @@
+-- Authors as their own rows. A post can have several (co-authors), ordered by `position`;
+-- `blog_posts.author` is kept as the joined byline for feeds and older clients.
+CREATE TABLE IF NOT EXISTS blog_authors (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    bio TEXT,
+    avatar TEXT,
+    -- e.g. {"github": "https://github.com/...", "x": "https://x.com/..."}
+    social_links JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP DEFAULT NOW(),
+    updated_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS blog_post_authors (
+    post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+    author_id INTEGER NOT NULL REFERENCES blog_authors(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (post_id, author_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_blog_post_authors_author ON blog_post_authors (author_id);
+
+-- Revisions remember who was credited, in byline order.
+ALTER TABLE blog_post_revisions ADD COLUMN IF NOT EXISTS author_ids INTEGER[] NOT NULL DEFAULT '{}';
+
+-- One author per distinct free-text byline, slugged the same way as everything else.
+-- Bylines the database locale can't slug (no ASCII letters under the C locale) get a hash.
+CREATE TEMP TABLE author_bylines ON COMMIT DROP AS
+SELECT DISTINCT BTRIM(author) AS byline,
+       COALESCE(
+           NULLIF(BTRIM(REGEXP_REPLACE(LOWER(BTRIM(author)), '[^[:alnum:]]+', '-', 'g'), '-'), ''),
+           'author-' || LEFT(MD5(BTRIM(author)), 8)
+       ) AS slug
+FROM blog_posts
+WHERE NULLIF(BTRIM(author), '') IS NOT NULL;
+
+INSERT INTO blog_authors (name, slug)
+SELECT MIN(byline), slug FROM author_bylines GROUP BY slug
+ON CONFLICT (slug) DO NOTHING;
+
+INSERT INTO blog_post_authors (post_id, author_id, position)
+SELECT p.id, a.id, 0
+FROM blog_posts p
+JOIN author_bylines b ON b.byline = BTRIM(p.author)
+JOIN blog_authors a ON a.slug = b.slug
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,20 @@
+-- Result of the last check of each outbound link, per item that contains it.
+-- `entity` is `post` (links in blog_posts.content), `project` (projects.link) or
+-- `social` (social_links.url). Rows for links that disappear are removed on the next run.
+CREATE TABLE IF NOT EXISTS link_checks (
+    id SERIAL PRIMARY KEY,
+    entity TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    -- Final HTTP status after redirects; NULL when no response arrived
+    status_code INTEGER,
+    -- Timeout, connection failure, redirect loop, …
+    error TEXT,
+    -- Where redirects ended, when different from `url`
+    final_url TEXT,
+    broken BOOLEAN NOT NULL DEFAULT false,
+    checked_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (entity, entity_id, url)
+);
+
+CREATE INDEX IF NOT EXISTS idx_link_checks_broken ON link_checks (entity, entity_id) WHERE broken;
@@ -0,0 +1,23 @@
+-- Manual ordering for the profile lists. Existing rows keep the order they were
+-- listed in: newest first for projects and experience, oldest first for skills
+-- and social links.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE skills ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE experience_timeline ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE social_links ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0;
+
+UPDATE projects p SET sort_order = o.position
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id DESC) - 1 AS position FROM projects) o
+WHERE p.id = o.id;
+
+UPDATE skills s SET sort_order = o.position
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id ASC) - 1 AS position FROM skills) o
+WHERE s.id = o.id;
+
+UPDATE experience_timeline e SET sort_order = o.position
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id DESC) - 1 AS position FROM experience_timeline) o
+WHERE e.id = o.id;
+
+UPDATE social_links l SET sort_order = o.position
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id ASC) - 1 AS position FROM social_links) o
+WHERE l.id = o.id;
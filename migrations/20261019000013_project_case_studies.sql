@@ -0,0 +1,38 @@
+-- Project detail pages: a slug to address them by, a Markdown case study and
+-- the role, team size and dates of the work.
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS slug TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS case_study TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS role TEXT;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS team_size INTEGER CHECK (team_size > 0);
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS start_date DATE;
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS end_date DATE;
+
+-- Slug existing projects from their titles, the same way as authors. Titles the
+-- database locale can't slug get a hash. In id order, each project takes the first
+-- of `base`, `base-2`, `base-3`, … that no project has yet, like post slugs.
+-- `reorder` is taken by the `/api/projects/reorder` route.
+DO $$
+DECLARE
+    project RECORD;
+    base TEXT;
+    candidate TEXT;
+    n INTEGER;
+BEGIN
+    FOR project IN SELECT id, title FROM projects WHERE slug IS NULL ORDER BY id LOOP
+        base := COALESCE(
+            NULLIF(BTRIM(REGEXP_REPLACE(LOWER(BTRIM(project.title)), '[^[:alnum:]]+', '-', 'g'), '-'), ''),
+            'project-' || LEFT(MD5(BTRIM(project.title)), 8)
+        );
+        candidate := base;
+        n := 2;
+        WHILE candidate = 'reorder' OR EXISTS (SELECT 1 FROM projects WHERE slug = candidate) LOOP
+            candidate := base || '-' || n;
+            n := n + 1;
+        END LOOP;
+        UPDATE projects SET slug = candidate WHERE id = project.id;
+    END LOOP;
+END
+$$;
+
+ALTER TABLE projects ALTER COLUMN slug SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_slug ON projects (slug);
@@ -38,7 +38,7 @@ where
         self.inner.invalidate(key).await;
     }
 
-    pub async fn _invalidate_all(&self) {
+    pub async fn invalidate_all(&self) {
         self.inner.invalidate_all();
     }
 }
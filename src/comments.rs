@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::feeds::escape_xml;
+use crate::models::CommentNode;
+
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_EMAIL_LEN: usize = 254;
+pub const MAX_BODY_LEN: usize = 5000;
+
+/// Check a submitted comment, returning the message for the 400 on failure.
+pub fn validate(name: &str, email: &str, body: &str) -> Result<(), String> {
+    let name = name.trim();
+    let email = email.trim();
+    let body = body.trim();
+
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Name must be 1-{} characters", MAX_NAME_LEN));
+    }
+    let valid_email = email.len() <= MAX_EMAIL_LEN
+        && !email.contains(char::is_whitespace)
+        && email
+            .split_once('@')
+            .map(|(local, domain)| !local.is_empty() && domain.contains('.') && !domain.starts_with('.'))
+            .unwrap_or(false);
+    if !valid_email {
+        return Err("A valid email is required".to_string());
+    }
+    if body.is_empty() || body.chars().count() > MAX_BODY_LEN {
+        return Err(format!("Comment must be 1-{} characters", MAX_BODY_LEN));
+    }
+
+    Ok(())
+}
+
+fn is_safe_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://")) && !url.contains(char::is_whitespace)
+}
+
+/// `**bold**`, `*italic*`, `` `code` `` and `[text](https://…)`. Everything else is escaped.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                out.push_str(&format!("<code>{}</code>", escape_xml(&after[..end])));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**").filter(|&end| end > 0) {
+                out.push_str(&format!("<strong>{}</strong>", render_inline(&after[..end])));
+                rest = &after[end + 2..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('*') {
+            if let Some(end) = after.find('*').filter(|&end| end > 0) {
+                out.push_str(&format!("<em>{}</em>", render_inline(&after[..end])));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            let link = after
+                .split_once("](")
+                .filter(|(label, _)| !label.is_empty() && !label.contains(['[', ']']))
+                .and_then(|(label, tail)| tail.split_once(')').map(|(url, tail)| (label, url, tail)))
+                .filter(|(_, url, _)| is_safe_url(url));
+            if let Some((label, url, tail)) = link {
+                out.push_str(&format!(
+                    "<a href=\"{}\" rel=\"nofollow ugc noopener\">{}</a>",
+                    escape_xml(url),
+                    render_inline(label)
+                ));
+                rest = tail;
+                continue;
+            }
+        }
+
+        out.push_str(&escape_xml(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+/// Markdown-lite to HTML: blank lines separate paragraphs, single newlines become
+/// `<br>`. No raw HTML survives; links are limited to http(s).
+pub fn render_markdown_lite(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let lines: Vec<String> = p.lines().map(|line| render_inline(line.trim())).collect();
+            format!("<p>{}</p>", lines.join("<br>"))
+        })
+        .collect()
+}
+
+/// Nest comments under their parents, keeping input order among siblings.
+/// Replies whose parent isn't in the list (not approved) are dropped with it.
+pub fn build_tree(comments: Vec<CommentNode>) -> Vec<CommentNode> {
+    let mut children: HashMap<Option<i32>, Vec<CommentNode>> = HashMap::new();
+    for comment in comments {
+        children.entry(comment.parent_id).or_default().push(comment);
+    }
+
+    fn attach(node: &mut CommentNode, children: &mut HashMap<Option<i32>, Vec<CommentNode>>) {
+        node.replies = children.remove(&Some(node.id)).unwrap_or_default();
+        for reply in &mut node.replies {
+            attach(reply, children);
+        }
+    }
+
+    let mut roots = children.remove(&None).unwrap_or_default();
+    for root in &mut roots {
+        attach(root, &mut children);
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32, parent_id: Option<i32>) -> CommentNode {
+        CommentNode {
+            id,
+            parent_id,
+            author_name: format!("user{}", id),
+            body_html: String::new(),
+            created_at: None,
+            replies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_markdown_lite_formatting() {
+        assert_eq!(
+            render_markdown_lite("**Great** post, *thanks*! Try `cargo run`.\nSecond line\n\nNew paragraph"),
+            "<p><strong>Great</strong> post, <em>thanks</em>! Try <code>cargo run</code>.<br>Second line</p><p>New paragraph</p>"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_lite_escapes_html() {
+        let html = render_markdown_lite("<script>alert(1)</script> `<b>`");
+        assert_eq!(html, "<p>&lt;script&gt;alert(1)&lt;/script&gt; <code>&lt;b&gt;</code></p>");
+    }
+
+    #[test]
+    fn test_render_markdown_lite_links() {
+        assert_eq!(
+            render_markdown_lite("See [the docs](https://docs.rs/axum)"),
+            "<p>See <a href=\"https://docs.rs/axum\" rel=\"nofollow ugc noopener\">the docs</a></p>"
+        );
+        // Only http(s) links are rendered; anything else stays as text.
+        assert_eq!(
+            render_markdown_lite("[x](javascript:alert(1))"),
+            "<p>[x](javascript:alert(1))</p>"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("Pasu", "pasu@example.com", "Nice!").is_ok());
+        assert!(validate(" ", "pasu@example.com", "Nice!").is_err());
+        assert!(validate("Pasu", "not-an-email", "Nice!").is_err());
+        assert!(validate("Pasu", "pasu@example.com", "   ").is_err());
+        assert!(validate("Pasu", "pasu@example.com", &"a".repeat(MAX_BODY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_build_tree() {
+        let tree = build_tree(vec![node(1, None), node(2, Some(1)), node(3, None), node(4, Some(2)), node(5, Some(99))]);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].id, 1);
+        assert_eq!(tree[0].replies[0].id, 2);
+        assert_eq!(tree[0].replies[0].replies[0].id, 4);
+        assert_eq!(tree[1].id, 3);
+        assert!(tree[1].replies.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use std::env;
+
+/// Public site settings used when rendering absolute links (feeds, sitemap).
+#[derive(Clone, Debug)]
+pub struct SiteConfig {
+    /// Frontend origin, e.g. `https://pasu.app` (no trailing slash)
+    pub base_url: String,
+    /// Origin this API is served from, used for feed self links
+    pub api_url: String,
+    pub title: String,
+    pub description: String,
+    /// Default locale; untranslated content is served in this language
+    pub language: String,
+    /// Every locale content can be translated into, default first
+    pub locales: Vec<String>,
+    pub author: String,
+    /// Path prefix of blog post pages on the frontend
+    pub blog_path: String,
+    /// Put the full post body in feeds instead of only the excerpt
+    pub feed_full_content: bool,
+    pub feed_limit: i64,
+    pub sitemap: SitemapConfig,
+}
+
+/// Which frontend pages go in the sitemap and how they are weighted.
+#[derive(Clone, Debug)]
+pub struct SitemapConfig {
+    /// Static profile sections, each served at `{base_url}/{section}`
+    pub sections: Vec<String>,
+    pub home_priority: f32,
+    pub section_priority: f32,
+    pub post_priority: f32,
+    pub archive_priority: f32,
+    /// URLs per sitemap file before switching to a sitemap index
+    pub max_urls: usize,
+}
+
+impl Default for SitemapConfig {
+    fn default() -> Self {
+        Self {
+            sections: ["about", "skills", "experience", "projects", "contact"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            home_priority: 1.0,
+            section_priority: 0.8,
+            post_priority: 0.7,
+            archive_priority: 0.5,
+            max_urls: 50_000,
+        }
+    }
+}
+
+/// Background checking of outbound links in posts, projects and social links.
+#[derive(Clone, Debug)]
+pub struct LinkCheckConfig {
+    /// Seconds between runs; 0 turns the background job off
+    pub interval_secs: u64,
+    /// Requests in flight at once
+    pub concurrency: usize,
+    /// Per-request timeout in seconds
+    pub timeout_secs: u64,
+    pub max_redirects: usize,
+}
+
+impl Default for LinkCheckConfig {
+    fn default() -> Self {
+        Self { interval_secs: 86_400, concurrency: 8, timeout_secs: 10, max_redirects: 5 }
+    }
+}
+
+impl LinkCheckConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            interval_secs: var_or("LINK_CHECK_INTERVAL_SECS", "").parse().unwrap_or(defaults.interval_secs),
+            concurrency: var_or("LINK_CHECK_CONCURRENCY", "").parse().unwrap_or(defaults.concurrency).max(1),
+            timeout_secs: var_or("LINK_CHECK_TIMEOUT_SECS", "").parse().unwrap_or(defaults.timeout_secs).max(1),
+            max_redirects: var_or("LINK_CHECK_MAX_REDIRECTS", "").parse().unwrap_or(defaults.max_redirects),
+        }
+    }
+}
+
+fn var_or(key: &str, default: &str) -> String {
+    env::var(key)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+impl SiteConfig {
+    pub fn from_env() -> Self {
+        let base_url = var_or("SITE_URL", "https://pasu.app").trim_end_matches('/').to_string();
+        let api_url = var_or("API_URL", &base_url).trim_end_matches('/').to_string();
+        let language = var_or("SITE_LANGUAGE", "en").to_lowercase();
+
+        let mut locales = vec![language.clone()];
+        for locale in var_or("SITE_LOCALES", "en,th").split(',') {
+            let locale = locale.trim().to_lowercase();
+            if !locale.is_empty() && !locales.contains(&locale) {
+                locales.push(locale);
+            }
+        }
+
+        Self {
+            api_url,
+            base_url,
+            title: var_or("SITE_TITLE", "PASU.APP"),
+            description: var_or("SITE_DESCRIPTION", "Pasu Nimsuwan's blog"),
+            language,
+            locales,
+            author: var_or("SITE_AUTHOR", "Pasu Nimsuwan"),
+            blog_path: format!("/{}", var_or("BLOG_PATH", "/blog").trim_matches('/')),
+            feed_full_content: var_or("FEED_FULL_CONTENT", "false") == "true",
+            feed_limit: var_or("FEED_LIMIT", "20").parse::<i64>().unwrap_or(20).clamp(1, 100),
+            sitemap: SitemapConfig::from_env(),
+        }
+    }
+
+    /// Absolute frontend URL of a top-level page (`""` for the home page).
+    pub fn page_url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_matches('/'))
+    }
+
+    /// Absolute frontend URL of a blog post.
+    pub fn post_url(&self, slug: &str) -> String {
+        format!("{}{}/{}", self.base_url, self.blog_path, slug)
+    }
+
+    /// Absolute frontend URL of a category archive page.
+    pub fn category_url(&self, slug: &str) -> String {
+        format!("{}{}/categories/{}", self.base_url, self.blog_path, slug)
+    }
+
+    /// Absolute frontend URL of a tag archive page.
+    pub fn tag_url(&self, slug: &str) -> String {
+        format!("{}{}/tags/{}", self.base_url, self.blog_path, slug)
+    }
+
+    /// Absolute frontend URL of a project's detail page.
+    pub fn project_url(&self, slug: &str) -> String {
+        format!("{}/projects/{}", self.base_url, slug)
+    }
+
+    /// Absolute URL of an API path.
+    pub fn api_path_url(&self, path: &str) -> String {
+        format!("{}/{}", self.api_url, path.trim_start_matches('/'))
+    }
+}
+
+fn priority_or(key: &str, default: f32) -> f32 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .filter(|p| (0.0..=1.0).contains(p))
+        .unwrap_or(default)
+}
+
+impl SitemapConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let sections = match env::var("SITEMAP_SECTIONS") {
+            Ok(list) => list
+                .split(',')
+                .map(|s| s.trim().trim_matches('/').to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Err(_) => defaults.sections,
+        };
+
+        Self {
+            sections,
+            home_priority: priority_or("SITEMAP_PRIORITY_HOME", defaults.home_priority),
+            section_priority: priority_or("SITEMAP_PRIORITY_SECTION", defaults.section_priority),
+            post_priority: priority_or("SITEMAP_PRIORITY_POST", defaults.post_priority),
+            archive_priority: priority_or("SITEMAP_PRIORITY_ARCHIVE", defaults.archive_priority),
+            // The sitemap protocol caps a single file at 50,000 URLs.
+            max_urls: var_or("SITEMAP_MAX_URLS", "50000")
+                .parse::<usize>()
+                .unwrap_or(defaults.max_urls)
+                .clamp(1, 50_000),
+        }
+    }
+}
@@ -2,6 +2,8 @@ use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 use std::env;
 
+use crate::highlight;
+
 pub type DbPool = Pool<Postgres>;
 
 pub async fn init_pool() -> Result<DbPool, sqlx::Error> {
@@ -12,3 +14,51 @@ pub async fn init_pool() -> Result<DbPool, sqlx::Error> {
         .connect(&database_url)
         .await
 }
+
+/// Apply pending migrations from `migrations/` (embedded at compile time).
+pub async fn run_migrations(pool: &DbPool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
+}
+
+/// Highlight code blocks in posts and post translations stored before highlighting
+/// moved to save time. Already highlighted content is unchanged, so only rows from
+/// before that are written; returns how many.
+pub async fn highlight_stored_posts(pool: &DbPool) -> Result<u64, sqlx::Error> {
+    let mut updated = 0;
+
+    let posts = sqlx::query_as::<_, (i32, String)>("SELECT id, content FROM blog_posts WHERE content LIKE '%<code%'")
+        .fetch_all(pool)
+        .await?;
+    for (id, content) in posts {
+        let highlighted = highlight::highlight_html(&content);
+        if highlighted != content {
+            // Not an edit, so `updated_at` stays as it was.
+            sqlx::query("UPDATE blog_posts SET content = $1 WHERE id = $2")
+                .bind(&highlighted)
+                .bind(id)
+                .execute(pool)
+                .await?;
+            updated += 1;
+        }
+    }
+
+    let translations = sqlx::query_as::<_, (i32, String, String)>(
+        "SELECT item_id, locale, fields->>'content' FROM blog_post_translations WHERE fields->>'content' LIKE '%<code%'"
+    )
+    .fetch_all(pool)
+    .await?;
+    for (item_id, locale, content) in translations {
+        let highlighted = highlight::highlight_html(&content);
+        if highlighted != content {
+            sqlx::query("UPDATE blog_post_translations SET fields = jsonb_set(fields, '{content}', to_jsonb($1::text)) WHERE item_id = $2 AND locale = $3")
+                .bind(&highlighted)
+                .bind(item_id)
+                .bind(&locale)
+                .execute(pool)
+                .await?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
@@ -0,0 +1,247 @@
+use std::io::{Cursor, Write};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+
+use crate::config::SiteConfig;
+use crate::frontmatter::{self, FrontMatter};
+use crate::models::BlogPost;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Bumped when the layout of the bundle or its front matter changes incompatibly.
+const FORMAT_VERSION: u32 = 1;
+
+/// A category, tag or author as listed in the manifest.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ExportTerm {
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestPost {
+    pub file: String,
+    pub id: i32,
+    pub uuid: Option<String>,
+    pub slug: String,
+    pub title: String,
+    pub published: bool,
+    /// `markdown`, or `html` for posts that were never written in Markdown
+    pub body_format: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub site: String,
+    pub base_url: String,
+    pub post_count: usize,
+    pub posts: Vec<ManifestPost>,
+    /// Every term with its display name; posts refer to them by slug
+    pub categories: Vec<ExportTerm>,
+    pub tags: Vec<ExportTerm>,
+    pub authors: Vec<ExportTerm>,
+}
+
+/// Stored timestamps are UTC.
+fn format_date(date: NaiveDateTime) -> String {
+    date.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Slugs from a post's `categories` / `tags` / `authors` JSON, in stored order.
+fn slugs(terms: &Option<serde_json::Value>) -> Vec<String> {
+    terms
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|t| t["slug"].as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+fn term_slugs(terms: &Option<serde_json::Value>) -> Vec<String> {
+    let mut slugs = slugs(terms);
+    slugs.sort();
+    slugs
+}
+
+/// The post's Markdown source, falling back to its HTML (valid Markdown too).
+fn post_body(post: &BlogPost) -> (&str, &'static str) {
+    match post.content_markdown.as_deref().filter(|md| !md.trim().is_empty()) {
+        Some(markdown) => (markdown, "markdown"),
+        None => (post.content.as_str(), "html"),
+    }
+}
+
+pub fn file_name(slug: &str) -> String {
+    format!("posts/{}.md", slug)
+}
+
+/// Front matter carrying everything stored about a post except its body.
+pub fn front_matter(post: &BlogPost) -> FrontMatter {
+    FrontMatter {
+        title: Some(post.title.clone()),
+        slug: Some(post.slug.clone()),
+        uuid: post.uuid.clone(),
+        date: post.published_at.map(format_date),
+        created: post.created_at.map(format_date),
+        updated: post.updated_at.map(format_date),
+        published: Some(post.published.unwrap_or(false)),
+        draft: None,
+        excerpt: Some(post.excerpt.clone()).filter(|e| !e.is_empty()),
+        author: post.author.clone(),
+        authors: slugs(&post.authors),
+        featured_image: post.featured_image.clone(),
+        reading_time: post.reading_time,
+        categories: term_slugs(&post.categories),
+        tags: term_slugs(&post.tags),
+        meta_title: post.meta_title.clone(),
+        meta_description: post.meta_description.clone(),
+        meta_keywords: post.meta_keywords.clone().unwrap_or_default(),
+    }
+}
+
+/// Zip with `manifest.json` and one `posts/<slug>.md` per post, in the format
+/// the import endpoint reads.
+pub fn build_bundle(
+    posts: &[BlogPost],
+    categories: Vec<ExportTerm>,
+    tags: Vec<ExportTerm>,
+    authors: Vec<ExportTerm>,
+    site: &SiteConfig,
+    exported_at: DateTime<Utc>,
+) -> anyhow::Result<Vec<u8>> {
+    let options = SimpleFileOptions::default();
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let mut entries = Vec::with_capacity(posts.len());
+    let mut documents = Vec::with_capacity(posts.len());
+
+    for post in posts {
+        let (body, body_format) = post_body(post);
+        let file = file_name(&post.slug);
+        documents.push((file.clone(), frontmatter::render(&front_matter(post), body).map_err(anyhow::Error::msg)?));
+        entries.push(ManifestPost {
+            file,
+            id: post.id,
+            uuid: post.uuid.clone(),
+            slug: post.slug.clone(),
+            title: post.title.clone(),
+            published: post.published.unwrap_or(false),
+            body_format,
+        });
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        exported_at: exported_at.to_rfc3339(),
+        site: site.title.clone(),
+        base_url: site.base_url.clone(),
+        post_count: entries.len(),
+        posts: entries,
+        categories,
+        tags,
+        authors,
+    };
+    writer.start_file(MANIFEST_FILE, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    for (file, text) in documents {
+        writer.start_file(file, options)?;
+        writer.write_all(text.as_bytes())?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SitemapConfig;
+    use crate::import;
+    use std::io::Read;
+
+    fn post(slug: &str, markdown: Option<&str>) -> BlogPost {
+        BlogPost {
+            id: 7,
+            uuid: Some("2f1d6a58-0c1e-4a53-9d7e-5c1b0b0f6a11".to_string()),
+            title: "Hello: Rust".to_string(),
+            slug: slug.to_string(),
+            excerpt: "Short".to_string(),
+            content: "<p>Body</p>".to_string(),
+            content_markdown: markdown.map(str::to_string),
+            featured_image: None,
+            author: Some("Pasu".to_string()),
+            published: Some(false),
+            published_at: NaiveDateTime::parse_from_str("2024-01-05 09:30:00", "%Y-%m-%d %H:%M:%S").ok(),
+            view_count: Some(3),
+            reading_time: Some(4),
+            meta_title: None,
+            meta_description: None,
+            meta_keywords: Some(vec!["rust".to_string()]),
+            created_at: None,
+            updated_at: None,
+            categories: Some(serde_json::json!([{ "id": 1, "name": "Tutorials", "slug": "tutorials" }])),
+            tags: Some(serde_json::json!([{ "id": 3, "name": "Web", "slug": "web" }, { "id": 2, "name": "Axum", "slug": "axum" }])),
+            authors: Some(serde_json::json!([{ "id": 2, "name": "Pasu", "slug": "pasu" }, { "id": 1, "name": "Jane", "slug": "jane" }])),
+            series: None,
+            previous_post: None,
+            next_post: None,
+            comments_closed: None,
+            toc: None,
+            locale: None,
+            available_locales: None,
+        }
+    }
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            base_url: "https://example.com".to_string(),
+            api_url: "https://api.example.com".to_string(),
+            title: "Example".to_string(),
+            description: "Example blog".to_string(),
+            language: "en".to_string(),
+            locales: vec!["en".to_string()],
+            author: "Pasu".to_string(),
+            blog_path: "/blog".to_string(),
+            feed_full_content: false,
+            feed_limit: 20,
+            sitemap: SitemapConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_front_matter() {
+        let fm = front_matter(&post("hello-rust", Some("Body")));
+        assert_eq!(fm.date.as_deref(), Some("2024-01-05T09:30:00Z"));
+        assert_eq!(fm.tags, ["axum", "web"]);
+        assert_eq!(fm.categories, ["tutorials"]);
+        assert_eq!(fm.authors, ["pasu", "jane"]);
+        assert_eq!(fm.published, Some(false));
+        assert!(fm.created.is_none());
+    }
+
+    #[test]
+    fn test_bundle_reimports() {
+        let posts = [post("hello-rust", Some("# Hi\n\nBody\n")), post("legacy", None)];
+        let terms = vec![ExportTerm { name: "Tutorials".to_string(), slug: "tutorials".to_string() }];
+        let bytes = build_bundle(&posts, terms, Vec::new(), Vec::new(), &site(), Utc::now()).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes.clone())).unwrap();
+        let mut manifest = String::new();
+        archive.by_name(MANIFEST_FILE).unwrap().read_to_string(&mut manifest).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(manifest["post_count"], 2);
+        assert_eq!(manifest["posts"][1]["body_format"], "html");
+        assert_eq!(manifest["categories"][0]["name"], "Tutorials");
+
+        let files = import::read_upload("export.zip", bytes).unwrap();
+        assert_eq!(files.len(), 2);
+        let doc = import::build_doc(files[0].text.as_ref().unwrap()).unwrap();
+        assert_eq!((doc.slug.as_str(), doc.title.as_str()), ("hello-rust", "Hello: Rust"));
+        assert_eq!(doc.content_markdown, "# Hi\n\nBody\n");
+        assert!(!doc.published);
+        assert_eq!(doc.tags, ["axum", "web"]);
+        assert_eq!(doc.authors, ["pasu", "jane"]);
+        assert_eq!(doc.published_at, posts[0].published_at);
+    }
+}
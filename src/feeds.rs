@@ -0,0 +1,338 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::config::SiteConfig;
+use crate::models::BlogPost;
+
+/// A rendered feed body with the validators sent for conditional requests.
+#[derive(Clone, Debug)]
+pub struct CachedFeed {
+    pub body: String,
+    pub etag: String,
+    pub last_modified: Option<NaiveDateTime>,
+}
+
+/// Channel-level metadata for one feed (the whole blog, a category or a tag).
+pub struct FeedChannel {
+    pub title: String,
+    pub description: String,
+    /// Frontend page the feed corresponds to
+    pub link: String,
+    /// Absolute URL the feed itself is served from
+    pub self_url: String,
+}
+
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Names from the aggregated `categories`/`tags` JSON on a post.
+pub fn term_names(terms: &Option<serde_json::Value>) -> Vec<String> {
+    terms
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("name").and_then(|n| n.as_str()))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Timestamps are stored without a zone and treated as UTC.
+fn post_date(post: &BlogPost) -> Option<NaiveDateTime> {
+    post.published_at.or(post.created_at)
+}
+
+fn post_updated(post: &BlogPost) -> Option<NaiveDateTime> {
+    post.updated_at.or_else(|| post_date(post))
+}
+
+/// Most recent modification across the posts, for `Last-Modified` and feed `updated`.
+pub fn last_modified(posts: &[BlogPost]) -> Option<NaiveDateTime> {
+    posts.iter().filter_map(post_updated).max()
+}
+
+fn post_id(post: &BlogPost, site: &SiteConfig) -> String {
+    match &post.uuid {
+        Some(uuid) => format!("urn:uuid:{}", uuid),
+        None => site.post_url(&post.slug),
+    }
+}
+
+pub fn render_rss(channel: &FeedChannel, posts: &[BlogPost], site: &SiteConfig) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    xml.push_str("<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(&channel.title)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(&channel.link)));
+    xml.push_str(&format!("<description>{}</description>\n", escape_xml(&channel.description)));
+    xml.push_str(&format!("<language>{}</language>\n", escape_xml(&site.language)));
+    xml.push_str(&format!(
+        "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_xml(&channel.self_url)
+    ));
+    if let Some(updated) = last_modified(posts) {
+        xml.push_str(&format!("<lastBuildDate>{}</lastBuildDate>\n", updated.and_utc().to_rfc2822()));
+    }
+
+    for post in posts {
+        let link = site.post_url(&post.slug);
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&post.title)));
+        xml.push_str(&format!("<link>{}</link>\n", escape_xml(&link)));
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"{}\">{}</guid>\n",
+            post.uuid.is_none(),
+            escape_xml(&post_id(post, site))
+        ));
+        if let Some(date) = post_date(post) {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", date.and_utc().to_rfc2822()));
+        }
+        let author = post.author.as_deref().unwrap_or(&site.author);
+        xml.push_str(&format!("<dc:creator>{}</dc:creator>\n", escape_xml(author)));
+        for name in term_names(&post.categories) {
+            xml.push_str(&format!("<category>{}</category>\n", escape_xml(&name)));
+        }
+        xml.push_str(&format!("<description>{}</description>\n", escape_xml(&post.excerpt)));
+        if site.feed_full_content {
+            xml.push_str(&format!("<content:encoded>{}</content:encoded>\n", escape_xml(&post.content)));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+pub fn render_atom(channel: &FeedChannel, posts: &[BlogPost], site: &SiteConfig) -> String {
+    let updated = last_modified(posts)
+        .map(|d| d.and_utc())
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n",
+        escape_xml(&site.language)
+    ));
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(&channel.title)));
+    xml.push_str(&format!("<subtitle>{}</subtitle>\n", escape_xml(&channel.description)));
+    xml.push_str(&format!("<id>{}</id>\n", escape_xml(&channel.self_url)));
+    xml.push_str(&format!("<link href=\"{}\"/>\n", escape_xml(&channel.link)));
+    xml.push_str(&format!("<link rel=\"self\" href=\"{}\"/>\n", escape_xml(&channel.self_url)));
+    xml.push_str(&format!("<updated>{}</updated>\n", updated));
+    xml.push_str(&format!("<author><name>{}</name></author>\n", escape_xml(&site.author)));
+
+    for post in posts {
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&post.title)));
+        xml.push_str(&format!("<link href=\"{}\"/>\n", escape_xml(&site.post_url(&post.slug))));
+        xml.push_str(&format!("<id>{}</id>\n", escape_xml(&post_id(post, site))));
+        if let Some(date) = post_date(post) {
+            xml.push_str(&format!("<published>{}</published>\n", date.and_utc().to_rfc3339()));
+        }
+        if let Some(date) = post_updated(post) {
+            xml.push_str(&format!("<updated>{}</updated>\n", date.and_utc().to_rfc3339()));
+        }
+        if let Some(author) = &post.author {
+            xml.push_str(&format!("<author><name>{}</name></author>\n", escape_xml(author)));
+        }
+        for name in term_names(&post.categories) {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", escape_xml(&name)));
+        }
+        xml.push_str(&format!("<summary>{}</summary>\n", escape_xml(&post.excerpt)));
+        if site.feed_full_content {
+            xml.push_str(&format!("<content type=\"html\">{}</content>\n", escape_xml(&post.content)));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+#[derive(Serialize, Debug)]
+pub struct JsonFeedAuthor {
+    pub name: String,
+}
+
+/// Top-level JSON Feed 1.1 document (https://www.jsonfeed.org/version/1.1/).
+#[derive(Serialize, Debug)]
+pub struct JsonFeed {
+    pub version: &'static str,
+    pub title: String,
+    pub home_page_url: String,
+    pub feed_url: String,
+    pub description: String,
+    pub language: String,
+    pub authors: Vec<JsonFeedAuthor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_url: Option<String>,
+    pub items: Vec<JsonFeedItem>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct JsonFeedItem {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub content_html: String,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<JsonFeedAuthor>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+pub fn json_feed_item(post: &BlogPost, site: &SiteConfig) -> JsonFeedItem {
+    let mut tags = term_names(&post.tags);
+    for category in term_names(&post.categories) {
+        if !tags.contains(&category) {
+            tags.push(category);
+        }
+    }
+
+    JsonFeedItem {
+        id: post.uuid.clone().unwrap_or_else(|| post.id.to_string()),
+        url: site.post_url(&post.slug),
+        title: post.title.clone(),
+        content_html: post.content.clone(),
+        summary: post.excerpt.clone(),
+        image: post.featured_image.clone().filter(|i| !i.is_empty()),
+        date_published: post_date(post).map(|d| d.and_utc().to_rfc3339()),
+        date_modified: post_updated(post).map(|d| d.and_utc().to_rfc3339()),
+        authors: post.author.iter().map(|name| JsonFeedAuthor { name: name.clone() }).collect(),
+        tags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SitemapConfig;
+
+    fn site(full: bool) -> SiteConfig {
+        SiteConfig {
+            base_url: "https://pasu.app".to_string(),
+            api_url: "https://api.pasu.app".to_string(),
+            title: "PASU.APP".to_string(),
+            description: "Blog".to_string(),
+            language: "en".to_string(),
+            locales: vec!["en".to_string(), "th".to_string()],
+            author: "Pasu".to_string(),
+            blog_path: "/blog".to_string(),
+            feed_full_content: full,
+            feed_limit: 20,
+            sitemap: SitemapConfig::default(),
+        }
+    }
+
+    fn channel() -> FeedChannel {
+        FeedChannel {
+            title: "PASU.APP".to_string(),
+            description: "Blog".to_string(),
+            link: "https://pasu.app/blog".to_string(),
+            self_url: "https://api.pasu.app/feed.xml".to_string(),
+        }
+    }
+
+    fn post() -> BlogPost {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_hms_opt(3, 4, 5).unwrap();
+        BlogPost {
+            id: 1,
+            uuid: Some("550e8400-e29b-41d4-a716-446655440000".to_string()),
+            title: "Rust & <Axum>".to_string(),
+            slug: "rust-axum".to_string(),
+            excerpt: "Short".to_string(),
+            content: "<p>Full body</p>".to_string(),
+            content_markdown: None,
+            featured_image: None,
+            author: Some("Pasu".to_string()),
+            published: Some(true),
+            published_at: Some(date),
+            view_count: None,
+            reading_time: None,
+            meta_title: None,
+            meta_description: None,
+            meta_keywords: None,
+            created_at: Some(date),
+            updated_at: Some(date),
+            categories: Some(serde_json::json!([{ "id": 1, "name": "Programming", "slug": "programming" }])),
+            tags: Some(serde_json::json!([])),
+            authors: None,
+            series: None,
+            previous_post: None,
+            next_post: None,
+            comments_closed: None,
+            toc: None,
+            locale: None,
+            available_locales: None,
+        }
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("a & <b> \"c\" 'd'"), "a &amp; &lt;b&gt; &quot;c&quot; &apos;d&apos;");
+    }
+
+    #[test]
+    fn test_render_rss_item() {
+        let xml = render_rss(&channel(), &[post()], &site(false));
+        assert!(xml.contains("<title>Rust &amp; &lt;Axum&gt;</title>"));
+        assert!(xml.contains("<link>https://pasu.app/blog/rust-axum</link>"));
+        assert!(xml.contains("<guid isPermaLink=\"false\">urn:uuid:550e8400-e29b-41d4-a716-446655440000</guid>"));
+        assert!(xml.contains("<pubDate>Tue, 2 Jan 2024 03:04:05 +0000</pubDate>"));
+        assert!(xml.contains("<category>Programming</category>"));
+        assert!(!xml.contains("content:encoded>"));
+    }
+
+    #[test]
+    fn test_render_rss_full_content() {
+        let xml = render_rss(&channel(), &[post()], &site(true));
+        assert!(xml.contains("<content:encoded>&lt;p&gt;Full body&lt;/p&gt;</content:encoded>"));
+    }
+
+    #[test]
+    fn test_render_atom_entry() {
+        let xml = render_atom(&channel(), &[post()], &site(false));
+        assert!(xml.contains("<link rel=\"self\" href=\"https://api.pasu.app/feed.xml\"/>"));
+        assert!(xml.contains("<updated>2024-01-02T03:04:05+00:00</updated>"));
+        assert!(xml.contains("<category term=\"Programming\"/>"));
+        assert!(xml.contains("<summary>Short</summary>"));
+    }
+
+    #[test]
+    fn test_json_feed_item() {
+        let item = json_feed_item(&post(), &site(false));
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["id"], "550e8400-e29b-41d4-a716-446655440000");
+        assert_eq!(json["url"], "https://pasu.app/blog/rust-axum");
+        assert_eq!(json["content_html"], "<p>Full body</p>");
+        assert_eq!(json["summary"], "Short");
+        assert_eq!(json["date_published"], "2024-01-02T03:04:05+00:00");
+        assert_eq!(json["tags"], serde_json::json!(["Programming"]));
+        assert!(json.get("image").is_none());
+    }
+}
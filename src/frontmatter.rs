@@ -0,0 +1,199 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Metadata block at the top of a Markdown post.
+/// Unknown keys are ignored so files written for static generators import as-is.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrontMatter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// Publish date: `2024-01-05`, `2024-01-05T09:30:00` or RFC 3339
+    #[serde(alias = "published_at", skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<bool>,
+    /// `draft: true` is the same as `published: false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft: Option<bool>,
+    #[serde(alias = "summary", skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// Byline; used as the only author when `authors` is empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Author slugs (or names), byline order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(alias = "image", alias = "cover", skip_serializing_if = "Option::is_none")]
+    pub featured_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_time: Option<i32>,
+    /// Category slugs (or names)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    /// Tag slugs (or names)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_description: Option<String>,
+    #[serde(default, alias = "keywords", skip_serializing_if = "Vec::is_empty")]
+    pub meta_keywords: Vec<String>,
+}
+
+impl FrontMatter {
+    /// Published unless `published: false` or `draft: true`.
+    pub fn is_published(&self) -> bool {
+        self.published.unwrap_or(!self.draft.unwrap_or(false))
+    }
+}
+
+/// Split a document into its front matter (YAML between `---` lines or TOML between
+/// `+++` lines) and Markdown body.
+pub fn parse(text: &str) -> Result<(FrontMatter, String), String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text).replace("\r\n", "\n");
+
+    let (fence, is_toml) = if text.starts_with("---\n") {
+        ("---", false)
+    } else if text.starts_with("+++\n") {
+        ("+++", true)
+    } else {
+        return Err("Missing front matter (expected a leading `---` or `+++` block)".to_string());
+    };
+
+    let rest = &text[fence.len() + 1..];
+    let (block, body) = if let Some(block) = rest.strip_prefix(&format!("{}\n", fence)) {
+        ("", block)
+    } else {
+        let end = rest
+            .find(&format!("\n{}\n", fence))
+            .map(|i| (i, i + fence.len() + 2))
+            .or_else(|| rest.strip_suffix(&format!("\n{}", fence)).map(|b| (b.len(), rest.len())))
+            .ok_or_else(|| format!("Unterminated front matter (no closing `{}`)", fence))?;
+        (&rest[..end.0], &rest[end.1..])
+    };
+
+    let front_matter = if is_toml { parse_toml(block)? } else { parse_yaml(block)? };
+    Ok((front_matter, body.trim_start_matches('\n').to_string()))
+}
+
+fn parse_yaml(block: &str) -> Result<FrontMatter, String> {
+    if block.trim().is_empty() {
+        return Ok(FrontMatter::default());
+    }
+    serde_yaml::from_str(block).map_err(|e| format!("Invalid YAML front matter: {}", e))
+}
+
+/// TOML dates are their own type; turn them into strings so they land in `date`.
+fn stringify_dates(value: toml::Value) -> toml::Value {
+    match value {
+        toml::Value::Datetime(date) => toml::Value::String(date.to_string()),
+        toml::Value::Array(items) => toml::Value::Array(items.into_iter().map(stringify_dates).collect()),
+        toml::Value::Table(table) => {
+            toml::Value::Table(table.into_iter().map(|(k, v)| (k, stringify_dates(v))).collect())
+        }
+        other => other,
+    }
+}
+
+fn parse_toml(block: &str) -> Result<FrontMatter, String> {
+    let table: toml::Table = block.parse().map_err(|e| format!("Invalid TOML front matter: {}", e))?;
+    stringify_dates(toml::Value::Table(table))
+        .try_into()
+        .map_err(|e| format!("Invalid TOML front matter: {}", e))
+}
+
+/// Write a document as YAML front matter followed by the Markdown body; `parse`
+/// reads it back unchanged.
+pub fn render(front_matter: &FrontMatter, body: &str) -> Result<String, String> {
+    let yaml = serde_yaml::to_string(front_matter).map_err(|e| format!("Could not write front matter: {}", e))?;
+    let body = body.trim_start_matches('\n');
+    if body.is_empty() {
+        return Ok(format!("---\n{}---\n", yaml));
+    }
+    Ok(format!("---\n{}---\n\n{}", yaml, body))
+}
+
+/// Parse a front matter date. Offsets are converted to UTC; bare dates are midnight.
+pub fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.naive_utc());
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(date);
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml() {
+        let doc = "---\ntitle: Hello, Rust\ntags: [rust, Axum]\ndraft: true\nkeywords:\n  - web\nsome_generator_key: 1\n---\n\n# Body\n";
+        let (fm, body) = parse(doc).unwrap();
+        assert_eq!(fm.title.as_deref(), Some("Hello, Rust"));
+        assert_eq!(fm.tags, ["rust", "Axum"]);
+        assert_eq!(fm.meta_keywords, ["web"]);
+        assert!(!fm.is_published());
+        assert_eq!(body, "# Body\n");
+    }
+
+    #[test]
+    fn test_parse_toml_with_native_date() {
+        let doc = "+++\ntitle = \"Hello\"\ndate = 2024-01-05T09:30:00Z\ncategories = [\"tutorials\"]\n+++\nBody";
+        let (fm, body) = parse(doc).unwrap();
+        assert_eq!(fm.date.as_deref(), Some("2024-01-05T09:30:00Z"));
+        assert_eq!(fm.categories, ["tutorials"]);
+        assert!(fm.is_published());
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("# No front matter").unwrap_err().contains("Missing front matter"));
+        assert!(parse("---\ntitle: x\n").unwrap_err().contains("Unterminated"));
+        assert!(parse("---\ntitle: [unclosed\n---\n").unwrap_err().contains("Invalid YAML"));
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let fm = FrontMatter {
+            title: Some("Colons: and \"quotes\"".to_string()),
+            slug: Some("colons".to_string()),
+            date: Some("2024-01-05T09:30:00Z".to_string()),
+            published: Some(false),
+            tags: vec!["rust".to_string(), "yes".to_string()],
+            meta_keywords: vec!["web".to_string()],
+            ..Default::default()
+        };
+        let text = render(&fm, "# Body\n\n---\n\nAfter a rule\n").unwrap();
+        assert!(text.starts_with("---\ntitle:"));
+        assert!(!text.contains("uuid"));
+
+        let (parsed, body) = parse(&text).unwrap();
+        assert_eq!(parsed, fm);
+        assert_eq!(body, "# Body\n\n---\n\nAfter a rule\n");
+        assert_eq!(render(&fm, "").unwrap(), format!("---\n{}---\n", serde_yaml::to_string(&fm).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("2024-01-05").unwrap().to_string(), "2024-01-05 00:00:00");
+        assert_eq!(parse_date("2024-01-05T09:30:00+07:00").unwrap().to_string(), "2024-01-05 02:30:00");
+        assert_eq!(parse_date("2024-01-05 09:30").unwrap().to_string(), "2024-01-05 09:30:00");
+        assert!(parse_date("5 Jan 2024").is_none());
+    }
+}
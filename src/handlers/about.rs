@@ -1,37 +1,54 @@
-use axum::{extract::State, Json};
+use axum::{
+    extract::{State, Query},
+    http::HeaderMap,
+    response::Response,
+    Json,
+};
 use serde::Deserialize;
+use crate::i18n::{self, TranslatableKind};
 use crate::models::About;
 use crate::error::AppError;
 use crate::state::AppState;
+use super::translations::{self, LangQuery};
 
 #[utoipa::path(
     get,
     path = "/api/about",
+    params(
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
     responses(
         (status = 200, description = "Get about info", body = About),
         (status = 404, description = "Not found")
     )
 )]
-pub async fn get_about(State(state): State<AppState>) -> Result<Json<About>, AppError> {
+pub async fn get_about(
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let cache_key = String::from("about");
-    
-    if let Some(cached) = state.about_cache.get(&cache_key).await {
-        return Ok(Json(cached));
-    }
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
 
-    let about = sqlx::query_as::<_, About>(
-        "SELECT id, user_bio, user_bio2, created_at, updated_at FROM about LIMIT 1"
-    )
-    .fetch_optional(&state.pool)
-    .await?;
+    let mut about = match state.about_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => {
+            let about = sqlx::query_as::<_, About>(
+                "SELECT id, user_bio, user_bio2, created_at, updated_at FROM about LIMIT 1"
+            )
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(AppError::NotFound)?;
 
-    match about {
-        Some(a) => {
-            state.about_cache.insert(cache_key.clone(), a.clone()).await;
-            Ok(Json(a))
+            state.about_cache.insert(cache_key.clone(), about.clone()).await;
+            about
         }
-        None => Err(AppError::NotFound),
-    }
+    };
+
+    let translations = translations::load(&state, TranslatableKind::About).await?;
+    i18n::localize(&mut about, &translations, &locale, &state.site);
+    let served = about.locale.clone().unwrap_or(locale);
+    Ok(i18n::localized_json(about, &served))
 }
 
 #[derive(Deserialize)]
@@ -0,0 +1,148 @@
+use axum::{
+    extract::{State, Path, Query},
+    http::HeaderMap,
+    Json,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::i18n::{self, TranslatableKind};
+use crate::models::{ArchiveMonth, ArchiveYear, BlogPost};
+use crate::error::AppError;
+use crate::state::AppState;
+use super::blog::POST_SELECT;
+use super::translations::{self, LangQuery};
+
+/// Group month rows (newest first) into years, keeping their order.
+pub(crate) fn group_by_year(months: Vec<ArchiveMonth>) -> Vec<ArchiveYear> {
+    let mut years: Vec<ArchiveYear> = Vec::new();
+    for month in months {
+        match years.last_mut() {
+            Some(year) if year.year == month.year => {
+                year.post_count += month.post_count;
+                year.months.push(month);
+            }
+            _ => years.push(ArchiveYear { year: month.year, post_count: month.post_count, months: vec![month] }),
+        }
+    }
+    years
+}
+
+/// `[start, end)` of a calendar month, or `None` for an invalid year/month.
+pub(crate) fn month_bounds(year: i32, month: u32) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let end = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    Some((start.and_hms_opt(0, 0, 0)?, end.and_hms_opt(0, 0, 0)?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/archive",
+    responses(
+        (status = 200, description = "Published post counts by year and month, newest first", body = [ArchiveYear])
+    )
+)]
+pub async fn get_archive(State(state): State<AppState>) -> Result<Json<Vec<ArchiveYear>>, AppError> {
+    let cache_key = String::from("archive");
+
+    if let Some(cached) = state.archive_cache.get(&cache_key).await {
+        return Ok(Json(cached));
+    }
+
+    let months = sqlx::query_as::<_, ArchiveMonth>(
+        r#"
+SELECT EXTRACT(YEAR FROM published_at)::int AS year,
+       EXTRACT(MONTH FROM published_at)::int AS month,
+       COUNT(*) AS post_count
+FROM blog_posts
+WHERE published = true AND scheduled = false AND published_at IS NOT NULL
+GROUP BY 1, 2
+ORDER BY 1 DESC, 2 DESC
+"#
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let archive = group_by_year(months);
+    state.archive_cache.insert(cache_key, archive.clone()).await;
+    Ok(Json(archive))
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ArchivePosts {
+    pub year: i32,
+    pub month: u32,
+    pub posts: Vec<BlogPost>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/archive/{year}/{month}",
+    params(
+        ("year" = i32, Path, description = "Year, e.g. 2024"),
+        ("month" = u32, Path, description = "Month, 1-12"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Posts published that month, newest first", body = ArchivePosts),
+        (status = 400, description = "Invalid year or month")
+    )
+)]
+pub async fn get_archive_month(
+    Path((year, month)): Path<(i32, u32)>,
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Json<ArchivePosts>, AppError> {
+    let (start, end) = month_bounds(year, month)
+        .ok_or_else(|| AppError::ValidationError(format!("Invalid month: {}/{}", year, month)))?;
+
+    let mut posts = sqlx::query_as::<_, BlogPost>(&format!(
+        "{} WHERE p.published = true AND p.scheduled = false AND p.published_at >= $1 AND p.published_at < $2 ORDER BY p.published_at DESC, p.id DESC",
+        POST_SELECT
+    ))
+    .bind(start)
+    .bind(end)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
+    let translations = translations::load(&state, TranslatableKind::Posts).await?;
+    for post in &mut posts {
+        i18n::localize(post, &translations, &locale, &state.site);
+    }
+
+    Ok(Json(ArchivePosts { year, month, posts }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(year: i32, month: i32, post_count: i64) -> ArchiveMonth {
+        ArchiveMonth { year, month, post_count }
+    }
+
+    #[test]
+    fn test_group_by_year() {
+        let years = group_by_year(vec![month(2025, 2, 1), month(2024, 12, 3), month(2024, 6, 2)]);
+        assert_eq!(years.len(), 2);
+        assert_eq!((years[0].year, years[0].post_count, years[0].months.len()), (2025, 1, 1));
+        assert_eq!((years[1].year, years[1].post_count), (2024, 5));
+        assert_eq!(years[1].months[0].month, 12);
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        let (start, end) = month_bounds(2024, 12).unwrap();
+        assert_eq!(start.to_string(), "2024-12-01 00:00:00");
+        assert_eq!(end.to_string(), "2025-01-01 00:00:00");
+        assert_eq!(month_bounds(2024, 2).unwrap().1.to_string(), "2024-03-01 00:00:00");
+        assert!(month_bounds(2024, 13).is_none());
+        assert!(month_bounds(2024, 0).is_none());
+    }
+}
@@ -0,0 +1,363 @@
+use axum::{
+    extract::{State, Path, Query},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use utoipa::ToSchema;
+use crate::models::{BlogAuthor, BlogPost};
+use crate::error::AppError;
+use crate::state::AppState;
+use super::blog::{self, slugify, TermPostsQuery};
+
+const AUTHOR_SELECT: &str = r#"
+SELECT a.id, a.name, a.slug, a.bio, a.avatar, a.social_links, a.created_at, a.updated_at,
+    (
+        SELECT COUNT(*) FROM blog_post_authors pa
+        JOIN blog_posts p ON p.id = pa.post_id
+        WHERE pa.author_id = a.id AND p.published = true AND p.scheduled = false
+    ) as post_count
+FROM blog_authors a
+"#;
+
+/// Ids of the authors named (by name or slug), creating the missing ones, in the
+/// order given. Names are matched before slugs: authors migrated from bylines may
+/// have a slug `slugify` wouldn't give them (e.g. a hash for non-ASCII names).
+pub(crate) async fn resolve_authors(conn: &mut PgConnection, names: &[String]) -> Result<Vec<i32>, AppError> {
+    let mut ids = Vec::new();
+    for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+        let slug = slugify(name);
+        if slug.is_empty() {
+            continue;
+        }
+        let existing = sqlx::query_scalar::<_, i32>(
+            "SELECT id FROM blog_authors WHERE LOWER(name) = LOWER($1) OR slug = $2 ORDER BY (LOWER(name) = LOWER($1)) DESC, id LIMIT 1"
+        )
+        .bind(name)
+        .bind(&slug)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                sqlx::query_scalar::<_, i32>(
+                    "INSERT INTO blog_authors (name, slug) VALUES ($1, $2) ON CONFLICT (slug) DO UPDATE SET slug = EXCLUDED.slug RETURNING id"
+                )
+                .bind(name)
+                .bind(&slug)
+                .fetch_one(&mut *conn)
+                .await?
+            }
+        };
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Names in a byline as `byline` writes it: "A, B".
+pub(crate) fn split_byline(byline: &str) -> Vec<String> {
+    byline.split(", ").map(str::trim).filter(|n| !n.is_empty()).map(str::to_string).collect()
+}
+
+/// Reject author ids that don't exist.
+pub(crate) async fn validate_authors(conn: &mut PgConnection, ids: &[i32]) -> Result<(), AppError> {
+    let unknown = sqlx::query_scalar::<_, i32>(
+        "SELECT DISTINCT u.id FROM UNNEST($1::int[]) AS u(id) WHERE NOT EXISTS (SELECT 1 FROM blog_authors a WHERE a.id = u.id) ORDER BY u.id"
+    )
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<String> = unknown.iter().map(|id| id.to_string()).collect();
+    Err(AppError::ValidationError(format!("Unknown author ids: {}", ids.join(", "))))
+}
+
+/// Byline for authors `ids`: their names in order, comma separated.
+pub(crate) async fn byline(conn: &mut PgConnection, ids: &[i32]) -> Result<Option<String>, AppError> {
+    let byline = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT STRING_AGG(a.name, ', ' ORDER BY u.position) FROM UNNEST($1::int[]) WITH ORDINALITY AS u(id, position) JOIN blog_authors a ON a.id = u.id"
+    )
+    .bind(ids)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(byline)
+}
+
+/// Rewrite the `author` byline of the given posts from their linked authors.
+async fn refresh_bylines(conn: &mut PgConnection, post_ids: &[i32]) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+UPDATE blog_posts p SET author = (
+    SELECT STRING_AGG(a.name, ', ' ORDER BY pa.position, a.id)
+    FROM blog_post_authors pa
+    JOIN blog_authors a ON a.id = pa.author_id
+    WHERE pa.post_id = p.id
+)
+WHERE p.id = ANY($1)
+"#
+    )
+    .bind(post_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Replace the post's authors with `ids` (byline order; unknown ids are skipped) and
+/// update its byline to match.
+pub(crate) async fn sync_authors(conn: &mut PgConnection, post_id: i32, ids: &[i32]) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM blog_post_authors WHERE post_id = $1")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        r#"
+INSERT INTO blog_post_authors (post_id, author_id, position)
+SELECT $1, u.id, u.position FROM UNNEST($2::int[]) WITH ORDINALITY AS u(id, position)
+WHERE EXISTS (SELECT 1 FROM blog_authors a WHERE a.id = u.id)
+ON CONFLICT DO NOTHING
+"#
+    )
+    .bind(post_id)
+    .bind(ids)
+    .execute(&mut *conn)
+    .await?;
+
+    refresh_bylines(conn, &[post_id]).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/authors",
+    responses(
+        (status = 200, description = "List blog authors", body = [BlogAuthor])
+    )
+)]
+pub async fn get_authors(State(state): State<AppState>) -> Result<Json<Vec<BlogAuthor>>, AppError> {
+    let authors = sqlx::query_as::<_, BlogAuthor>(&format!("{} ORDER BY a.name", AUTHOR_SELECT))
+        .fetch_all(&state.pool)
+        .await?;
+
+    Ok(Json(authors))
+}
+
+/// An author with one page of their published posts, newest first.
+#[derive(Serialize, ToSchema)]
+pub struct AuthorPosts {
+    pub author: BlogAuthor,
+    pub posts: Vec<BlogPost>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/authors/{slug}",
+    params(
+        ("slug" = String, Path, description = "Author slug"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Posts per page (default 10, max 50)"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Author profile with a page of their published posts", body = AuthorPosts),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_author(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<TermPostsQuery>,
+    headers: HeaderMap,
+) -> Result<Json<AuthorPosts>, AppError> {
+    let author = sqlx::query_as::<_, BlogAuthor>(&format!("{} WHERE a.slug = $1", AUTHOR_SELECT))
+        .bind(&slug)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let (posts, page, per_page, total) =
+        blog::term_posts(&state, &headers, "blog_post_authors", "author_id", author.id, &query).await?;
+
+    Ok(Json(AuthorPosts { author, posts, page, per_page, total, total_pages: blog::total_pages(total, per_page) }))
+}
+
+// ========== Admin Author CRUD ==========
+
+#[derive(Deserialize)]
+pub struct AuthorPayload {
+    pub name: String,
+    pub slug: Option<String>,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    /// Site name → profile URL, e.g. `{"github": "https://github.com/..."}`
+    pub social_links: Option<serde_json::Value>,
+}
+
+fn author_slug(payload: &AuthorPayload) -> String {
+    match &payload.slug {
+        Some(s) if !s.is_empty() => slugify(s),
+        _ => slugify(&payload.name),
+    }
+}
+
+/// Social links as stored: an object of non-empty strings (`{}` when absent).
+fn social_links(value: Option<&serde_json::Value>) -> Result<serde_json::Value, AppError> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Ok(serde_json::json!({}));
+    };
+    let links = value
+        .as_object()
+        .ok_or_else(|| AppError::ValidationError("social_links must be an object of site → URL".to_string()))?;
+    if let Some((site, _)) = links.iter().find(|(_, url)| url.as_str().is_none_or(|u| u.trim().is_empty())) {
+        return Err(AppError::ValidationError(format!("social_links.{} must be a non-empty URL", site)));
+    }
+    Ok(value.clone())
+}
+
+fn validate_author(payload: &AuthorPayload) -> Result<(String, serde_json::Value), AppError> {
+    if payload.name.trim().is_empty() {
+        return Err(AppError::ValidationError("Author name is required".to_string()));
+    }
+    let slug = author_slug(payload);
+    if slug.is_empty() {
+        return Err(AppError::ValidationError("Could not derive a slug from the name".to_string()));
+    }
+    Ok((slug, social_links(payload.social_links.as_ref())?))
+}
+
+// POST /api/blog/admin/authors
+pub async fn create_author(
+    State(state): State<AppState>,
+    Json(payload): Json<AuthorPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let (slug, links) = validate_author(&payload)?;
+
+    let id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO blog_authors (name, slug, bio, avatar, social_links) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (slug) DO NOTHING RETURNING id"
+    )
+    .bind(payload.name.trim())
+    .bind(&slug)
+    .bind(&payload.bio)
+    .bind(&payload.avatar)
+    .bind(&links)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::ValidationError(format!("An author with slug '{}' already exists", slug)))?;
+
+    Ok(Json(serde_json::json!({ "success": true, "id": id })))
+}
+
+// PUT /api/blog/admin/authors/:id - Bylines of their posts follow a rename
+pub async fn update_author(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<AuthorPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let (slug, links) = validate_author(&payload)?;
+
+    let mut tx = state.pool.begin().await?;
+    let taken = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM blog_authors WHERE slug = $1 AND id <> $2)")
+        .bind(&slug)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if taken {
+        return Err(AppError::ValidationError(format!("An author with slug '{}' already exists", slug)));
+    }
+
+    let result = sqlx::query(
+        "UPDATE blog_authors SET name = $1, slug = $2, bio = $3, avatar = $4, social_links = $5, updated_at = NOW() WHERE id = $6"
+    )
+    .bind(payload.name.trim())
+    .bind(&slug)
+    .bind(&payload.bio)
+    .bind(&payload.avatar)
+    .bind(&links)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    let post_ids = sqlx::query_scalar::<_, i32>("SELECT post_id FROM blog_post_authors WHERE author_id = $1")
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+    refresh_bylines(&mut tx, &post_ids).await?;
+    tx.commit().await?;
+
+    state.invalidate_posts().await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// DELETE /api/blog/admin/authors/:id - Their posts stay, credited to any co-authors
+pub async fn delete_author(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let mut tx = state.pool.begin().await?;
+
+    let post_ids = sqlx::query_scalar::<_, i32>("SELECT post_id FROM blog_post_authors WHERE author_id = $1")
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM blog_authors WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    refresh_bylines(&mut tx, &post_ids).await?;
+    tx.commit().await?;
+
+    state.invalidate_posts().await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(name: &str, slug: Option<&str>, social_links: Option<serde_json::Value>) -> AuthorPayload {
+        AuthorPayload { name: name.to_string(), slug: slug.map(str::to_string), bio: None, avatar: None, social_links }
+    }
+
+    #[test]
+    fn test_validate_author() {
+        let (slug, links) = validate_author(&payload("Jane Doe", None, None)).unwrap();
+        assert_eq!(slug, "jane-doe");
+        assert_eq!(links, serde_json::json!({}));
+
+        let (slug, _) = validate_author(&payload("Jane", Some("Jane D."), None)).unwrap();
+        assert_eq!(slug, "jane-d");
+
+        assert!(validate_author(&payload("  ", None, None)).is_err());
+        let bad = payload("Jane", None, Some(serde_json::json!({ "github": "" })));
+        assert!(matches!(validate_author(&bad), Err(AppError::ValidationError(m)) if m.contains("github")));
+        assert!(validate_author(&payload("Jane", None, Some(serde_json::json!(["x"])))).is_err());
+    }
+
+    #[test]
+    fn test_split_byline() {
+        assert_eq!(split_byline("Jane Doe, Pasu"), ["Jane Doe", "Pasu"]);
+        assert_eq!(split_byline("Pasu"), ["Pasu"]);
+        assert!(split_byline(" ").is_empty());
+    }
+}
@@ -1,11 +1,29 @@
-use axum::{extract::{State, Path}, Json};
-use serde::Deserialize;
-use crate::models::{BlogPost, BlogCategory, BlogTag};
+use axum::{
+    extract::{State, Path, Query, RawQuery},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::HashSet;
+use sqlx::PgConnection;
+use crate::models::{BlogPost, BlogCategory, BlogTag, PopularPost, RelatedPost};
 use crate::error::AppError;
 use crate::state::AppState;
+use crate::highlight;
+use crate::i18n::{self, TranslatableKind};
+use crate::preview;
+use crate::toc;
+use crate::views;
+use super::revisions;
+use super::authors;
+use super::series;
+use super::tags;
+use super::translations::{self, LangQuery};
 
 /// Generate a URL-friendly slug from text
-fn slugify(text: &str) -> String {
+pub(crate) fn slugify(text: &str) -> String {
     text.to_lowercase()
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
@@ -16,18 +34,40 @@ fn slugify(text: &str) -> String {
         .join("-")
 }
 
-#[utoipa::path(
-    get,
-    path = "/api/blog/posts",
-    responses(
-        (status = 200, description = "Get blog posts", body = [BlogPost])
+/// `base`, or `base-2`, `base-3`, … whichever is the first not in `taken`.
+pub(crate) fn next_free_slug(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded range always yields a free slug")
+}
+
+/// A slug no other post uses now or used before (old slugs keep redirecting).
+/// `post_id` is the post being updated, whose own slugs don't count as taken.
+async fn unique_slug(conn: &mut PgConnection, base: &str, post_id: Option<i32>) -> Result<String, AppError> {
+    let pattern = format!("{}-%", base.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+    let taken: HashSet<String> = sqlx::query_scalar::<_, String>(
+        r#"SELECT slug FROM blog_posts WHERE (slug = $1 OR slug LIKE $2) AND id IS DISTINCT FROM $3
+UNION
+SELECT slug FROM blog_slug_history WHERE (slug = $1 OR slug LIKE $2) AND post_id IS DISTINCT FROM $3"#
     )
-)]
-pub async fn get_posts(State(state): State<AppState>) -> Result<Json<Vec<BlogPost>>, AppError> {
-    let posts = sqlx::query_as::<_, BlogPost>(
-        r#"
+    .bind(base)
+    .bind(&pattern)
+    .bind(post_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+
+    Ok(next_free_slug(base, &taken))
+}
 
-SELECT p.id, p.uuid::text as uuid, p.title, p.slug, p.excerpt, p.content, p.content_markdown, p.featured_image, p.author, p.published, p.published_at, p.view_count, p.reading_time, p.meta_title, p.meta_description, p.meta_keywords, p.created_at, p.updated_at,
+/// Post columns plus aggregated categories, tags and authors; append a WHERE clause on `p`.
+pub(crate) const POST_SELECT: &str = r#"
+SELECT p.id, p.uuid::text as uuid, p.title, p.slug, p.excerpt, p.content, p.content_markdown, p.featured_image, p.author, p.published, p.published_at, p.view_count, p.reading_time, p.meta_title, p.meta_description, p.meta_keywords, p.created_at, p.updated_at, p.comments_closed,
     (
         SELECT COALESCE(json_agg(jsonb_build_object(
         'id', c.id,
@@ -49,70 +89,222 @@ SELECT p.id, p.uuid::text as uuid, p.title, p.slug, p.excerpt, p.content, p.cont
         FROM blog_post_tags pt
         JOIN blog_tags t ON pt.tag_id = t.id
         WHERE pt.post_id = p.id
-    ) as tags
+    ) as tags,
+    (
+        SELECT COALESCE(json_agg(jsonb_build_object(
+        'id', a.id,
+        'name', a.name,
+        'slug', a.slug,
+        'avatar', a.avatar
+        ) ORDER BY pa.position, a.id), '[]'::json)
+        FROM blog_post_authors pa
+        JOIN blog_authors a ON pa.author_id = a.id
+        WHERE pa.post_id = p.id
+    ) as authors
 FROM blog_posts p
- WHERE p.published = true ORDER BY p.published_at DESC
-"#
+"#;
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/posts",
+    params(
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Get blog posts", body = [BlogPost])
     )
-    .fetch_all(&state.pool)
-    .await?;
+)]
+pub async fn get_posts(
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cache_key = String::from("posts");
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
 
-    Ok(Json(posts))
+    let mut posts = match state.posts_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => {
+            let posts = sqlx::query_as::<_, BlogPost>(&format!(
+                "{} WHERE p.published = true AND p.scheduled = false ORDER BY p.published_at DESC",
+                POST_SELECT
+            ))
+            .fetch_all(&state.pool)
+            .await?;
+
+            state.posts_cache.insert(cache_key, posts.clone()).await;
+            posts
+        }
+    };
+
+    let translations = translations::load(&state, TranslatableKind::Posts).await?;
+    for post in &mut posts {
+        i18n::localize(post, &translations, &locale, &state.site);
+    }
+    Ok(i18n::localized_json(posts, &locale))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PostQuery {
+    /// Preview token issued by the admin API; allows reading an unpublished post
+    pub preview: Option<String>,
+    /// Requested locale; takes precedence over `Accept-Language`
+    pub lang: Option<String>,
+    /// Category slug; previous/next posts are picked from that category only
+    pub category: Option<String>,
+}
+
+/// `previous_post` / `next_post` columns for a post row aliased `post`: the nearest
+/// older and newer published posts by `published_at`, within category slug `$4` if
+/// given. A previewed draft sits where it would if published now.
+const NEIGHBOUR_COLUMNS: &str = r#"
+    (
+        SELECT json_build_object('id', n.id, 'title', n.title, 'slug', n.slug, 'published_at', n.published_at)
+        FROM blog_posts n
+        WHERE n.published = true AND n.scheduled = false
+          AND (n.published_at, n.id) < (COALESCE(post.published_at, LOCALTIMESTAMP), post.id)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM blog_post_categories pc JOIN blog_categories c ON c.id = pc.category_id
+              WHERE pc.post_id = n.id AND c.slug = $4
+          ))
+        ORDER BY n.published_at DESC, n.id DESC
+        LIMIT 1
+    ) as previous_post,
+    (
+        SELECT json_build_object('id', n.id, 'title', n.title, 'slug', n.slug, 'published_at', n.published_at)
+        FROM blog_posts n
+        WHERE n.published = true AND n.scheduled = false
+          AND (n.published_at, n.id) > (COALESCE(post.published_at, LOCALTIMESTAMP), post.id)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM blog_post_categories pc JOIN blog_categories c ON c.id = pc.category_id
+              WHERE pc.post_id = n.id AND c.slug = $4
+          ))
+        ORDER BY n.published_at ASC, n.id ASC
+        LIMIT 1
+    ) as next_post
+"#;
+
+fn preview_secret() -> Result<String, AppError> {
+    std::env::var("PREVIEW_SECRET")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::InternalError(anyhow::anyhow!("PREVIEW_SECRET not set")))
+}
+
+/// Point a request for a retired slug at the post's current URL.
+async fn redirect_old_slug(state: &AppState, slug: &str, raw_query: Option<&str>) -> Result<Response, AppError> {
+    let current = sqlx::query_scalar::<_, String>(
+        "SELECT p.slug FROM blog_slug_history h JOIN blog_posts p ON p.id = h.post_id WHERE h.slug = $1 AND p.published = true AND p.scheduled = false"
+    )
+    .bind(slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    // Keep `?lang=`, `?category=` and the like on the new URL.
+    let location = match raw_query.filter(|q| !q.is_empty()) {
+        Some(q) => format!("/api/blog/posts/{}?{}", current, q),
+        None => format!("/api/blog/posts/{}", current),
+    };
+
+    Ok((
+        StatusCode::MOVED_PERMANENTLY,
+        [(header::LOCATION, location)],
+        Json(SlugRedirect { redirect: true, slug: current }),
+    )
+        .into_response())
+}
+
+/// Body of the 301 sent for an old slug, for clients that don't follow redirects.
+#[derive(Serialize, ToSchema)]
+pub struct SlugRedirect {
+    pub redirect: bool,
+    pub slug: String,
 }
 
 #[utoipa::path(
     get,
     path = "/api/blog/posts/{slug}",
     params(
-        ("slug" = String, Path, description = "Post slug")
+        ("slug" = String, Path, description = "Post slug"),
+        ("preview" = Option<String>, Query, description = "Preview token for an unpublished post"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language"),
+        ("category" = Option<String>, Query, description = "Category slug to pick previous/next posts from")
     ),
     responses(
         (status = 200, description = "Get blog post by slug", body = BlogPost),
+        (status = 301, description = "Slug was renamed; `Location` points at the current one", body = SlugRedirect),
+        (status = 401, description = "Invalid or expired preview token"),
         (status = 404, description = "Not found")
     )
 )]
 pub async fn get_post_by_slug(
     Path(slug): Path<String>,
     State(state): State<AppState>,
-) -> Result<Json<BlogPost>, AppError> {
-    let post = sqlx::query_as::<_, BlogPost>(
-        r#"
+    Query(query): Query<PostQuery>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    // A valid preview token also unlocks the draft it was issued for.
+    let claims = match &query.preview {
+        Some(token) => {
+            let claims = preview::verify(token, &preview_secret()?, chrono::Utc::now().timestamp())
+                .map_err(|_| AppError::AuthError)?;
+            Some(claims)
+        }
+        None => None,
+    };
 
-SELECT p.id, p.uuid::text as uuid, p.title, p.slug, p.excerpt, p.content, p.content_markdown, p.featured_image, p.author, p.published, p.published_at, p.view_count, p.reading_time, p.meta_title, p.meta_description, p.meta_keywords, p.created_at, p.updated_at,
-    (
-        SELECT COALESCE(json_agg(jsonb_build_object(
-        'id', c.id,
-        'name', c.name,
-        'slug', c.slug,
-        'icon', c.icon,
-        'color', c.color
-        )), '[]'::json)
-        FROM blog_post_categories pc
-        JOIN blog_categories c ON pc.category_id = c.id
-        WHERE pc.post_id = p.id
-    ) as categories,
-    (
-        SELECT COALESCE(json_agg(jsonb_build_object(
-        'id', t.id,
-        'name', t.name,
-        'slug', t.slug
-        )), '[]'::json)
-        FROM blog_post_tags pt
-        JOIN blog_tags t ON pt.tag_id = t.id
-        WHERE pt.post_id = p.id
-    ) as tags
-FROM blog_posts p
- WHERE (p.slug = $1 OR p.uuid::text = $1) AND p.published = true
-"#
-    )
+    let post = sqlx::query_as::<_, BlogPost>(&format!(
+        r#"SELECT post.*, {}, {}
+FROM ({}
+WHERE (p.slug = $1 OR p.uuid::text = $1)
+  AND ((p.published = true AND p.scheduled = false) OR (p.id = $2 AND p.preview_version = $3))
+) post"#,
+        series::SERIES_COLUMN,
+        NEIGHBOUR_COLUMNS,
+        POST_SELECT
+    ))
     .bind(&slug)
+    .bind(claims.as_ref().map(|c| c.post_id))
+    .bind(claims.as_ref().map(|c| c.version))
+    .bind(query.category.as_deref().filter(|c| !c.is_empty()))
     .fetch_optional(&state.pool)
     .await?;
 
-    match post {
-        Some(p) => Ok(Json(p)),
-        None => Err(AppError::NotFound),
+    let mut post = match post {
+        Some(post) => post,
+        None => return redirect_old_slug(&state, &slug, raw_query.as_deref()).await,
+    };
+
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
+    let translations = translations::load(&state, TranslatableKind::Posts).await?;
+    i18n::localize(&mut post, &translations, &locale, &state.site);
+    for neighbour in [&mut post.previous_post, &mut post.next_post].into_iter().flatten() {
+        i18n::localize_link_title(neighbour, &translations, &locale);
+    }
+    let served = post.locale.clone().unwrap_or(locale);
+    let alternates = i18n::alternate_links(
+        &state.site.post_url(&post.slug),
+        post.available_locales.as_deref().unwrap_or_default(),
+        &state.site,
+    );
+
+    let (content, toc) = toc::from_html(&post.content);
+    post.content = content;
+    post.toc = Some(toc);
+
+    let mut response = i18n::localized_json(post, &served);
+    let headers = response.headers_mut();
+    if let Ok(link) = HeaderValue::from_str(&alternates) {
+        headers.insert(header::LINK, link);
+    }
+    if claims.is_some() {
+        headers.insert(HeaderName::from_static("x-robots-tag"), HeaderValue::from_static("noindex"));
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-store"));
     }
+
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -129,9 +321,10 @@ pub async fn get_categories(State(state): State<AppState>) -> Result<Json<Vec<Bl
         return Ok(Json(cached));
     }
 
-    let categories = sqlx::query_as::<_, BlogCategory>(
-        "SELECT id, name, slug, description, icon, color, created_at, updated_at FROM blog_categories ORDER BY name ASC"
-    )
+    let categories = sqlx::query_as::<_, BlogCategory>(&format!(
+        "SELECT c.id, c.name, c.slug, c.description, c.icon, c.color, c.created_at, c.updated_at, {} FROM blog_categories c ORDER BY c.name ASC",
+        CATEGORY_POST_COUNT
+    ))
     .fetch_all(&state.pool)
     .await?;
 
@@ -142,25 +335,449 @@ pub async fn get_categories(State(state): State<AppState>) -> Result<Json<Vec<Bl
 #[utoipa::path(
     get,
     path = "/api/blog/tags",
+    params(
+        ("hide_empty" = Option<bool>, Query, description = "Leave out tags without published posts"),
+        ("prefix" = Option<String>, Query, description = "Autocomplete: tags whose name or slug starts with this, most used first"),
+        ("limit" = Option<usize>, Query, description = "Maximum autocomplete suggestions (default 10, max 50)")
+    ),
     responses(
         (status = 200, description = "Get blog tags", body = [BlogTag])
     )
 )]
-pub async fn get_tags(State(state): State<AppState>) -> Result<Json<Vec<BlogTag>>, AppError> {
+pub async fn get_tags(
+    State(state): State<AppState>,
+    Query(query): Query<TagListQuery>,
+) -> Result<Json<Vec<BlogTag>>, AppError> {
     let cache_key = String::from("tags");
-    
-    if let Some(cached) = state.tags_cache.get(&cache_key).await {
-        return Ok(Json(cached));
+
+    let tags = match state.tags_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => {
+            let tags = sqlx::query_as::<_, BlogTag>(&format!(
+                "SELECT t.id, t.name, t.slug, t.created_at, t.updated_at, {} FROM blog_tags t ORDER BY t.name ASC",
+                TAG_POST_COUNT
+            ))
+            .fetch_all(&state.pool)
+            .await?;
+
+            state.tags_cache.insert(cache_key, tags.clone()).await;
+            tags
+        }
+    };
+
+    let tags: Vec<BlogTag> = if query.hide_empty.unwrap_or(false) {
+        tags.into_iter().filter(|t| t.post_count.unwrap_or(0) > 0).collect()
+    } else {
+        tags
+    };
+
+    match query.prefix.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(prefix) => Ok(Json(autocomplete(tags, prefix, query.limit.unwrap_or(10).clamp(1, 50)))),
+        None => Ok(Json(tags)),
     }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TagListQuery {
+    pub hide_empty: Option<bool>,
+    pub prefix: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Tags whose name or slug starts with `prefix` (case-insensitive), most used first.
+fn autocomplete(tags: Vec<BlogTag>, prefix: &str, limit: usize) -> Vec<BlogTag> {
+    let prefix = prefix.to_lowercase();
+    let mut matches: Vec<BlogTag> = tags
+        .into_iter()
+        .filter(|t| t.name.to_lowercase().starts_with(&prefix) || t.slug.starts_with(&prefix))
+        .collect();
+    // Stable: ties keep the alphabetical order of the listing.
+    matches.sort_by_key(|t| std::cmp::Reverse(t.post_count.unwrap_or(0)));
+    matches.truncate(limit);
+    matches
+}
+
+/// `post_count` column for a query over `blog_categories c`.
+const CATEGORY_POST_COUNT: &str = "(SELECT COUNT(*) FROM blog_post_categories pc JOIN blog_posts p ON p.id = pc.post_id WHERE pc.category_id = c.id AND p.published = true AND p.scheduled = false) AS post_count";
+
+/// `post_count` column for a query over `blog_tags t`.
+const TAG_POST_COUNT: &str = "(SELECT COUNT(*) FROM blog_post_tags pt JOIN blog_posts p ON p.id = pt.post_id WHERE pt.tag_id = t.id AND p.published = true AND p.scheduled = false) AS post_count";
+
+#[derive(Deserialize, Debug)]
+pub struct TermPostsQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub lang: Option<String>,
+}
+
+/// One page of a category's published posts, newest first.
+#[derive(Serialize, ToSchema)]
+pub struct CategoryPosts {
+    pub category: BlogCategory,
+    pub posts: Vec<BlogPost>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+/// One page of a tag's published posts, newest first.
+#[derive(Serialize, ToSchema)]
+pub struct TagPosts {
+    pub tag: BlogTag,
+    pub posts: Vec<BlogPost>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+/// Highest page served; keeps `(page - 1) * per_page` far from overflowing.
+const MAX_PAGE: i64 = 10_000;
+
+/// Page number from the query, clamped so the offset can't overflow. Pages start at 1.
+pub(crate) fn clamp_page(page: Option<i64>) -> i64 {
+    page.unwrap_or(1).clamp(1, MAX_PAGE)
+}
 
-    let tags = sqlx::query_as::<_, BlogTag>(
-        "SELECT id, name, slug, created_at, updated_at FROM blog_tags ORDER BY name ASC"
+/// Page number and size from the query, clamped. Pages start at 1.
+pub(crate) fn page_bounds(page: Option<i64>, per_page: Option<i64>) -> (i64, i64) {
+    (clamp_page(page), per_page.unwrap_or(10).clamp(1, 50))
+}
+
+pub(crate) fn total_pages(total: i64, per_page: i64) -> i64 {
+    (total + per_page - 1) / per_page
+}
+
+/// Published posts linked to `term_id` through `join_table` (`blog_post_categories`,
+/// `blog_post_tags` or `blog_post_authors`), localized, plus the total count.
+pub(crate) async fn term_posts(
+    state: &AppState,
+    headers: &HeaderMap,
+    join_table: &str,
+    term_column: &str,
+    term_id: i32,
+    query: &TermPostsQuery,
+) -> Result<(Vec<BlogPost>, i64, i64, i64), AppError> {
+    let (page, per_page) = page_bounds(query.page, query.per_page);
+    let filter = format!(
+        "p.published = true AND p.scheduled = false AND EXISTS (SELECT 1 FROM {join_table} j WHERE j.post_id = p.id AND j.{term_column} = $1)"
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM blog_posts p WHERE {}", filter))
+        .bind(term_id)
+        .fetch_one(&state.pool)
+        .await?;
+
+    let mut posts = sqlx::query_as::<_, BlogPost>(&format!(
+        "{} WHERE {} ORDER BY p.published_at DESC, p.id DESC LIMIT $2 OFFSET $3",
+        POST_SELECT, filter
+    ))
+    .bind(term_id)
+    .bind(per_page)
+    .bind((page - 1) * per_page)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let locale = i18n::request_locale(query.lang.as_deref(), headers, &state.site);
+    let translations = translations::load(state, TranslatableKind::Posts).await?;
+    for post in &mut posts {
+        i18n::localize(post, &translations, &locale, &state.site);
+    }
+
+    Ok((posts, page, per_page, total))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/categories/{slug}",
+    params(
+        ("slug" = String, Path, description = "Category slug"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Posts per page (default 10, max 50)"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Category with a page of its published posts", body = CategoryPosts),
+        (status = 404, description = "Not found")
     )
+)]
+pub async fn get_category_posts(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<TermPostsQuery>,
+    headers: HeaderMap,
+) -> Result<Json<CategoryPosts>, AppError> {
+    let category = sqlx::query_as::<_, BlogCategory>(&format!(
+        "SELECT c.id, c.name, c.slug, c.description, c.icon, c.color, c.created_at, c.updated_at, {} FROM blog_categories c WHERE c.slug = $1",
+        CATEGORY_POST_COUNT
+    ))
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let (posts, page, per_page, total) =
+        term_posts(&state, &headers, "blog_post_categories", "category_id", category.id, &query).await?;
+
+    Ok(Json(CategoryPosts { category, posts, page, per_page, total, total_pages: total_pages(total, per_page) }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/tags/{slug}",
+    params(
+        ("slug" = String, Path, description = "Tag slug"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("per_page" = Option<i64>, Query, description = "Posts per page (default 10, max 50)"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Tag with a page of its published posts", body = TagPosts),
+        (status = 301, description = "Tag was renamed or merged; `Location` points at the current one", body = SlugRedirect),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_tag_posts(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<TermPostsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let tag = sqlx::query_as::<_, BlogTag>(&format!(
+        "SELECT t.id, t.name, t.slug, t.created_at, t.updated_at, {} FROM blog_tags t WHERE t.slug = $1",
+        TAG_POST_COUNT
+    ))
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    let tag = match tag {
+        Some(tag) => tag,
+        None => {
+            let current = tags::current_slug(&state, &slug).await?.ok_or(AppError::NotFound)?;
+            return Ok((
+                StatusCode::MOVED_PERMANENTLY,
+                [(header::LOCATION, format!("/api/blog/tags/{}", current))],
+                Json(SlugRedirect { redirect: true, slug: current }),
+            )
+                .into_response());
+        }
+    };
+
+    let (posts, page, per_page, total) =
+        term_posts(&state, &headers, "blog_post_tags", "tag_id", tag.id, &query).await?;
+
+    Ok(Json(TagPosts { tag, posts, page, per_page, total, total_pages: total_pages(total, per_page) }).into_response())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ThemeQuery {
+    pub theme: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/highlight.css",
+    params(
+        ("theme" = Option<String>, Query, description = "`light` (default) or `dark`")
+    ),
+    responses(
+        (status = 200, description = "Stylesheet for highlighted code blocks", body = String, content_type = "text/css")
+    )
+)]
+pub async fn get_highlight_css(Query(query): Query<ThemeQuery>) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "text/css; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        highlight::theme_css(query.theme.as_deref().unwrap_or("light")),
+    )
+}
+
+// ========== View Counting ==========
+
+#[derive(Serialize, ToSchema)]
+pub struct ViewResponse {
+    pub success: bool,
+    /// False when the visitor was already counted today or looks like a bot
+    pub counted: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/blog/posts/{slug}/view",
+    params(
+        ("slug" = String, Path, description = "Post slug")
+    ),
+    responses(
+        (status = 200, description = "Record a unique view", body = ViewResponse),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn record_view(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ViewResponse>, AppError> {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    let post_id = sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM blog_posts WHERE (slug = $1 OR uuid::text = $1) AND published = true AND scheduled = false"
+    )
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    if views::is_bot(user_agent) {
+        return Ok(Json(ViewResponse { success: true, counted: false }));
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    let visitor = state.views.visitor(&views::client_ip(&headers), user_agent, today);
+    let counted = state.views.record(post_id, visitor, today);
+
+    Ok(Json(ViewResponse { success: true, counted }))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PopularQuery {
+    pub days: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/posts/popular",
+    params(
+        ("days" = Option<i64>, Query, description = "Window in days (default 30, max 90)"),
+        ("limit" = Option<i64>, Query, description = "Number of posts (default 5, max 50)")
+    ),
+    responses(
+        (status = 200, description = "Most viewed published posts in the window", body = [PopularPost])
+    )
+)]
+pub async fn get_popular_posts(
+    State(state): State<AppState>,
+    Query(query): Query<PopularQuery>,
+) -> Result<Json<Vec<PopularPost>>, AppError> {
+    let days = query.days.unwrap_or(30).clamp(1, views::VIEW_RETENTION_DAYS);
+    let limit = query.limit.unwrap_or(5).clamp(1, 50);
+
+    let posts = sqlx::query_as::<_, PopularPost>(
+        r#"
+SELECT p.id, p.title, p.slug, p.excerpt, p.featured_image, p.published_at, p.view_count, v.window_views
+FROM blog_posts p
+JOIN (
+    SELECT post_id, COUNT(*) AS window_views
+    FROM blog_post_views
+    WHERE view_date > CURRENT_DATE - $1::int
+    GROUP BY post_id
+) v ON v.post_id = p.id
+WHERE p.published = true AND p.scheduled = false
+ORDER BY v.window_views DESC, p.published_at DESC
+LIMIT $2
+"#
+    )
+    .bind(days as i32)
+    .bind(limit)
     .fetch_all(&state.pool)
     .await?;
 
-    state.tags_cache.insert(cache_key, tags.clone()).await;
-    Ok(Json(tags))
+    Ok(Json(posts))
+}
+
+// ========== Related Posts ==========
+
+#[derive(Deserialize, Debug)]
+pub struct RelatedQuery {
+    pub limit: Option<i64>,
+}
+
+/// Title similarity only counts when the optional `pg_trgm` extension is installed.
+async fn has_trigram(state: &AppState) -> Result<bool, AppError> {
+    Ok(sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'pg_trgm')")
+        .fetch_one(&state.pool)
+        .await?)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/posts/{slug}/related",
+    params(
+        ("slug" = String, Path, description = "Post slug"),
+        ("limit" = Option<i64>, Query, description = "Number of posts (default 4, max 20)")
+    ),
+    responses(
+        (status = 200, description = "Published posts sharing the most tags and categories", body = [RelatedPost]),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_related_posts(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<RelatedQuery>,
+) -> Result<Json<Vec<RelatedPost>>, AppError> {
+    let limit = query.limit.unwrap_or(4).clamp(1, 20);
+    let cache_key = format!("{}:{}", slug, limit);
+
+    if let Some(cached) = state.related_cache.get(&cache_key).await {
+        return Ok(Json(cached));
+    }
+
+    let (post_id, title) = sqlx::query_as::<_, (i32, String)>(
+        "SELECT id, title FROM blog_posts WHERE (slug = $1 OR uuid::text = $1) AND published = true AND scheduled = false"
+    )
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    // Weak title matches are noise, so similarity only adds to the score above 0.3.
+    let trigram = has_trigram(&state).await?;
+    let similarity = if trigram {
+        "CASE WHEN similarity(p.title, $3) >= 0.3 THEN similarity(p.title, $3)::float8 * 2.0 ELSE 0.0 END"
+    } else {
+        "0.0"
+    };
+
+    let sql = format!(
+        r#"
+SELECT p.id, p.title, p.slug, p.excerpt, p.featured_image, p.published_at, t.shared_tags, c.shared_categories, s.score
+FROM blog_posts p
+CROSS JOIN LATERAL (
+    SELECT COUNT(*) AS shared_tags
+    FROM blog_post_tags a JOIN blog_post_tags b ON b.tag_id = a.tag_id
+    WHERE a.post_id = $1 AND b.post_id = p.id
+) t
+CROSS JOIN LATERAL (
+    SELECT COUNT(*) AS shared_categories
+    FROM blog_post_categories a JOIN blog_post_categories b ON b.category_id = a.category_id
+    WHERE a.post_id = $1 AND b.post_id = p.id
+) c
+CROSS JOIN LATERAL (
+    SELECT (t.shared_tags * 2 + c.shared_categories)::float8 + {} AS score
+) s
+WHERE p.id <> $1 AND p.published = true AND p.scheduled = false AND s.score > 0
+ORDER BY s.score DESC, p.published_at DESC NULLS LAST
+LIMIT $2
+"#,
+        similarity
+    );
+
+    let mut related = sqlx::query_as::<_, RelatedPost>(&sql).bind(post_id).bind(limit);
+    if trigram {
+        related = related.bind(&title);
+    }
+    let posts = related.fetch_all(&state.pool).await?;
+
+    state.related_cache.insert(cache_key, posts.clone()).await;
+    Ok(Json(posts))
 }
 
 // ========== Admin Blog Post CRUD ==========
@@ -182,6 +799,8 @@ pub struct BlogPostPayload {
     pub meta_keywords: Option<Vec<String>>,
     pub categories: Option<Vec<i32>>,
     pub tags: Option<Vec<i32>>,
+    /// Author ids in byline order; without it the `author` byline is matched to an author
+    pub authors: Option<Vec<i32>>,
 }
 
 // GET /api/blog/admin/posts/:id - Get post by ID (admin, includes unpublished)
@@ -189,36 +808,215 @@ pub async fn get_post_by_id(
     Path(id): Path<i32>,
     State(state): State<AppState>,
 ) -> Result<Json<BlogPost>, AppError> {
-    let post = sqlx::query_as::<_, BlogPost>(
-        "SELECT id, uuid::text as uuid, title, slug, excerpt, content, content_markdown, featured_image, author, published, published_at, view_count, reading_time, meta_title, meta_description, meta_keywords, created_at, updated_at FROM blog_posts WHERE id = $1"
+    let mut conn = state.pool.acquire().await?;
+    Ok(Json(fetch_post(&mut conn, id).await?))
+}
+
+// GET /api/blog/admin/scheduled - Posts waiting for their published_at, soonest first
+pub async fn get_scheduled_posts(State(state): State<AppState>) -> Result<Json<Vec<BlogPost>>, AppError> {
+    let posts = sqlx::query_as::<_, BlogPost>(&format!(
+        "{} WHERE p.published = true AND p.scheduled = true ORDER BY p.published_at ASC",
+        POST_SELECT
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(posts))
+}
+
+#[derive(Deserialize)]
+pub struct PreviewLinkPayload {
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct PreviewLinkResponse {
+    pub token: String,
+    pub url: String,
+    pub expires_at: String,
+}
+
+// POST /api/blog/admin/posts/:id/preview - Issue a signed, expiring preview link
+pub async fn create_preview_link(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    payload: Option<Json<PreviewLinkPayload>>,
+) -> Result<Json<PreviewLinkResponse>, AppError> {
+    let secret = preview_secret()?;
+    let hours = payload
+        .and_then(|p| p.0.expires_in_hours)
+        .unwrap_or(72)
+        .clamp(1, 24 * 30);
+
+    let (slug, version) = sqlx::query_as::<_, (String, i32)>(
+        "SELECT slug, preview_version FROM blog_posts WHERE id = $1"
     )
     .bind(id)
     .fetch_optional(&state.pool)
-    .await?;
+    .await?
+    .ok_or(AppError::NotFound)?;
 
-    match post {
-        Some(p) => Ok(Json(p)),
-        None => Err(AppError::NotFound),
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(hours);
+    let token = preview::sign(
+        &preview::PreviewClaims { post_id: id, version, expires_at: expires_at.timestamp() },
+        &secret,
+    );
+
+    Ok(Json(PreviewLinkResponse {
+        url: format!("/api/blog/posts/{}?preview={}", slug, token),
+        token,
+        expires_at: expires_at.to_rfc3339(),
+    }))
+}
+
+// DELETE /api/blog/admin/posts/:id/preview - Revoke every preview link issued for the post
+pub async fn revoke_preview_links(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("UPDATE blog_posts SET preview_version = preview_version + 1 WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Describe category/tag ids that don't exist, or `None` when all are known.
+fn unknown_ids_message(categories: &[i32], tags: &[i32]) -> Option<String> {
+    let join = |ids: &[i32]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+
+    let mut parts = Vec::new();
+    if !categories.is_empty() {
+        parts.push(format!("Unknown category ids: {}", join(categories)));
+    }
+    if !tags.is_empty() {
+        parts.push(format!("Unknown tag ids: {}", join(tags)));
+    }
+
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+/// Reject the payload if it references categories or tags that don't exist.
+async fn validate_terms(
+    conn: &mut PgConnection,
+    categories: &[i32],
+    tags: &[i32],
+) -> Result<(), AppError> {
+    let missing = |table: &str| format!(
+        "SELECT DISTINCT u.id FROM UNNEST($1::int[]) AS u(id) WHERE NOT EXISTS (SELECT 1 FROM {} t WHERE t.id = u.id) ORDER BY u.id",
+        table
+    );
+
+    let unknown_categories = sqlx::query_scalar::<_, i32>(&missing("blog_categories"))
+        .bind(categories)
+        .fetch_all(&mut *conn)
+        .await?;
+    let unknown_tags = sqlx::query_scalar::<_, i32>(&missing("blog_tags"))
+        .bind(tags)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    match unknown_ids_message(&unknown_categories, &unknown_tags) {
+        Some(message) => Err(AppError::ValidationError(message)),
+        None => Ok(()),
+    }
+}
+
+/// Replace the post's category and tag links with the given ids.
+async fn sync_terms(
+    conn: &mut PgConnection,
+    post_id: i32,
+    categories: &[i32],
+    tags: &[i32],
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM blog_post_categories WHERE post_id = $1")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("INSERT INTO blog_post_categories (post_id, category_id) SELECT $1, id FROM blog_categories WHERE id = ANY($2)")
+        .bind(post_id)
+        .bind(categories)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query("DELETE FROM blog_post_tags WHERE post_id = $1")
+        .bind(post_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("INSERT INTO blog_post_tags (post_id, tag_id) SELECT $1, id FROM blog_tags WHERE id = ANY($2)")
+        .bind(post_id)
+        .bind(tags)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Author ids for the payload: `authors` as given, or the author named by the
+/// free-text `author` (created if new) for clients that only send a byline.
+/// Author ids for a create (`post_id` None) or update. Without `authors`, an update
+/// keeps the post's current authors unless the byline was edited; otherwise the
+/// byline's comma-separated names are resolved.
+async fn payload_authors(conn: &mut PgConnection, payload: &BlogPostPayload, post_id: Option<i32>) -> Result<Vec<i32>, AppError> {
+    if let Some(ids) = &payload.authors {
+        authors::validate_authors(conn, ids).await?;
+        return Ok(ids.clone());
     }
+
+    let byline = payload.author.as_deref().map(str::trim).filter(|a| !a.is_empty());
+    if let Some(post_id) = post_id {
+        let current = sqlx::query_scalar::<_, i32>("SELECT author_id FROM blog_post_authors WHERE post_id = $1 ORDER BY position, author_id")
+            .bind(post_id)
+            .fetch_all(&mut *conn)
+            .await?;
+        let current_byline = authors::byline(conn, &current).await?;
+        if byline.is_none() || byline == current_byline.as_deref() {
+            return Ok(current);
+        }
+    }
+
+    let names: Vec<String> = byline.map(authors::split_byline).unwrap_or_default();
+    authors::resolve_authors(conn, &names).await
+}
+
+/// A post with its categories and tags, regardless of publish state.
+async fn fetch_post(conn: &mut PgConnection, id: i32) -> Result<BlogPost, AppError> {
+    sqlx::query_as::<_, BlogPost>(&format!("{} WHERE p.id = $1", POST_SELECT))
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::NotFound)
 }
 
 // POST /api/blog/posts - Create new blog post
 pub async fn create_post(
     State(state): State<AppState>,
     Json(payload): Json<BlogPostPayload>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let slug = match &payload.slug {
+) -> Result<Json<BlogPost>, AppError> {
+    let base_slug = match &payload.slug {
         Some(s) if !s.is_empty() => s.clone(),
         _ => slugify(&payload.title),
     };
+    let categories = payload.categories.clone().unwrap_or_default();
+    let tags = payload.tags.clone().unwrap_or_default();
 
-    let result = sqlx::query_scalar::<_, i32>(
-        "INSERT INTO blog_posts (title, slug, excerpt, content, content_markdown, featured_image, author, published, published_at, reading_time, meta_title, meta_description, meta_keywords) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9::timestamp, NOW()), $10, $11, $12, $13) RETURNING id"
+    let mut tx = state.pool.begin().await?;
+    validate_terms(&mut tx, &categories, &tags).await?;
+    let author_ids = payload_authors(&mut tx, &payload, None).await?;
+    let slug = unique_slug(&mut tx, &base_slug, None).await?;
+
+    let post_id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO blog_posts (title, slug, excerpt, content, content_markdown, featured_image, author, published, published_at, scheduled, reading_time, meta_title, meta_description, meta_keywords) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9::timestamp, NOW()), $8 AND COALESCE($9::timestamp, NOW()) > NOW(), $10, $11, $12, $13) RETURNING id"
     )
     .bind(&payload.title)
     .bind(&slug)
     .bind(&payload.excerpt)
-    .bind(&payload.content)
+    .bind(highlight::highlight_html(&payload.content))
     .bind(&payload.content_markdown)
     .bind(&payload.featured_image)
     .bind(&payload.author)
@@ -228,34 +1026,17 @@ pub async fn create_post(
     .bind(&payload.meta_title)
     .bind(&payload.meta_description)
     .bind(&payload.meta_keywords)
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
     .await?;
 
-    let post_id = result;
+    sync_terms(&mut tx, post_id, &categories, &tags).await?;
+    authors::sync_authors(&mut tx, post_id, &author_ids).await?;
+    let post = fetch_post(&mut tx, post_id).await?;
+    tx.commit().await?;
 
-    // Handle categories
-    if let Some(categories) = &payload.categories {
-        for cat_id in categories {
-            let _ = sqlx::query("INSERT INTO blog_post_categories (post_id, category_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
-                .bind(post_id)
-                .bind(cat_id)
-                .execute(&state.pool)
-                .await;
-        }
-    }
+    state.invalidate_posts().await;
 
-    // Handle tags
-    if let Some(tags) = &payload.tags {
-        for tag_id in tags {
-            let _ = sqlx::query("INSERT INTO blog_post_tags (post_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
-                .bind(post_id)
-                .bind(tag_id)
-                .execute(&state.pool)
-                .await;
-        }
-    }
-
-    Ok(Json(serde_json::json!({ "success": true, "id": post_id })))
+    Ok(Json(post))
 }
 
 // PUT /api/blog/admin/posts/:id - Update blog post
@@ -263,19 +1044,48 @@ pub async fn update_post(
     Path(id): Path<i32>,
     State(state): State<AppState>,
     Json(payload): Json<BlogPostPayload>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    let slug = match &payload.slug {
+) -> Result<Json<BlogPost>, AppError> {
+    let base_slug = match &payload.slug {
         Some(s) if !s.is_empty() => s.clone(),
         _ => slugify(&payload.title),
     };
+    let categories = payload.categories.clone().unwrap_or_default();
+    let tags = payload.tags.clone().unwrap_or_default();
+
+    let mut tx = state.pool.begin().await?;
+    validate_terms(&mut tx, &categories, &tags).await?;
+    let author_ids = payload_authors(&mut tx, &payload, Some(id)).await?;
+
+    let old_slug = sqlx::query_scalar::<_, String>("SELECT slug FROM blog_posts WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let slug = unique_slug(&mut tx, &base_slug, Some(id)).await?;
+
+    if slug != old_slug {
+        // Old links keep working; a post taking back one of its old slugs drops it from history.
+        sqlx::query("INSERT INTO blog_slug_history (slug, post_id) VALUES ($1, $2) ON CONFLICT (slug) DO UPDATE SET post_id = EXCLUDED.post_id, created_at = NOW()")
+            .bind(&old_slug)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM blog_slug_history WHERE slug = $1")
+            .bind(&slug)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // Keep the version being replaced so the edit can be reviewed or undone.
+    revisions::snapshot_post(&mut *tx, id).await?;
 
     let result = sqlx::query(
-        "UPDATE blog_posts SET title = $1, slug = $2, excerpt = $3, content = $4, content_markdown = $5, featured_image = $6, author = $7, published = $8, published_at = COALESCE($9::timestamp, published_at), reading_time = $10, meta_title = $11, meta_description = $12, meta_keywords = $13, updated_at = NOW() WHERE id = $14"
+        "UPDATE blog_posts SET title = $1, slug = $2, excerpt = $3, content = $4, content_markdown = $5, featured_image = $6, author = $7, published = $8, published_at = COALESCE($9::timestamp, published_at), scheduled = $8 AND COALESCE($9::timestamp, published_at) > NOW(), reading_time = $10, meta_title = $11, meta_description = $12, meta_keywords = $13, updated_at = NOW() WHERE id = $14"
     )
     .bind(&payload.title)
     .bind(&slug)
     .bind(&payload.excerpt)
-    .bind(&payload.content)
+    .bind(highlight::highlight_html(&payload.content))
     .bind(&payload.content_markdown)
     .bind(&payload.featured_image)
     .bind(&payload.author)
@@ -286,46 +1096,21 @@ pub async fn update_post(
     .bind(&payload.meta_description)
     .bind(&payload.meta_keywords)
     .bind(id)
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await?;
 
     if result.rows_affected() == 0 {
         return Err(AppError::NotFound);
     }
 
-    // Update categories: delete old, insert new
-    sqlx::query("DELETE FROM blog_post_categories WHERE post_id = $1")
-        .bind(id)
-        .execute(&state.pool)
-        .await?;
+    sync_terms(&mut tx, id, &categories, &tags).await?;
+    authors::sync_authors(&mut tx, id, &author_ids).await?;
+    let post = fetch_post(&mut tx, id).await?;
+    tx.commit().await?;
 
-    if let Some(categories) = &payload.categories {
-        for cat_id in categories {
-            let _ = sqlx::query("INSERT INTO blog_post_categories (post_id, category_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
-                .bind(id)
-                .bind(cat_id)
-                .execute(&state.pool)
-                .await;
-        }
-    }
+    state.invalidate_posts().await;
 
-    // Update tags: delete old, insert new
-    sqlx::query("DELETE FROM blog_post_tags WHERE post_id = $1")
-        .bind(id)
-        .execute(&state.pool)
-        .await?;
-
-    if let Some(tags) = &payload.tags {
-        for tag_id in tags {
-            let _ = sqlx::query("INSERT INTO blog_post_tags (post_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
-                .bind(id)
-                .bind(tag_id)
-                .execute(&state.pool)
-                .await;
-        }
-    }
-
-    Ok(Json(serde_json::json!({ "success": true })))
+    Ok(Json(post))
 }
 
 // DELETE /api/blog/admin/posts/:id
@@ -346,6 +1131,8 @@ pub async fn delete_post(
         return Err(AppError::NotFound);
     }
 
+    state.invalidate_posts().await;
+
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
@@ -397,7 +1184,8 @@ pub async fn update_category(
         return Err(AppError::NotFound);
     }
 
-    state.categories_cache.invalidate(&String::from("categories")).await;
+    // Posts embed their categories' names and slugs.
+    state.invalidate_posts().await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -416,7 +1204,8 @@ pub async fn delete_category(
         return Err(AppError::NotFound);
     }
 
-    state.categories_cache.invalidate(&String::from("categories")).await;
+    // Posts embed their categories' names and slugs.
+    state.invalidate_posts().await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -444,23 +1233,35 @@ pub async fn create_tag(
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+// A changed slug is kept in the tag's slug history so old links redirect.
 pub async fn update_tag(
     Path(id): Path<i32>,
     State(state): State<AppState>,
     Json(payload): Json<TagPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let result = sqlx::query("UPDATE blog_tags SET name = $1, slug = $2, updated_at = NOW() WHERE id = $3")
+    let mut tx = state.pool.begin().await?;
+
+    let old_slug = sqlx::query_scalar::<_, String>("SELECT slug FROM blog_tags WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    sqlx::query("UPDATE blog_tags SET name = $1, slug = $2, updated_at = NOW() WHERE id = $3")
         .bind(&payload.name)
         .bind(&payload.slug)
         .bind(id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await?;
 
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound);
+    if old_slug != payload.slug {
+        tags::record_old_slug(&mut tx, &old_slug, id).await?;
     }
 
-    state.tags_cache.invalidate(&String::from("tags")).await;
+    tx.commit().await?;
+
+    // Posts embed their tags' names and slugs.
+    state.invalidate_posts().await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -479,7 +1280,62 @@ pub async fn delete_tag(
         return Err(AppError::NotFound);
     }
 
-    state.tags_cache.invalidate(&String::from("tags")).await;
+    // Posts embed their tags' names and slugs.
+    state.invalidate_posts().await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_free_slug() {
+        let taken: HashSet<String> = ["my-post", "my-post-2", "other"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(next_free_slug("fresh", &taken), "fresh");
+        assert_eq!(next_free_slug("my-post", &taken), "my-post-3");
+        assert_eq!(next_free_slug("other", &taken), "other-2");
+    }
+
+    #[test]
+    fn test_unknown_ids_message() {
+        assert_eq!(unknown_ids_message(&[], &[]), None);
+        assert_eq!(unknown_ids_message(&[4, 9], &[]).unwrap(), "Unknown category ids: 4, 9");
+        assert_eq!(
+            unknown_ids_message(&[4], &[12]).unwrap(),
+            "Unknown category ids: 4; Unknown tag ids: 12"
+        );
+    }
+
+    #[test]
+    fn test_page_bounds() {
+        assert_eq!(page_bounds(None, None), (1, 10));
+        assert_eq!(page_bounds(Some(0), Some(500)), (1, 50));
+        assert_eq!(page_bounds(Some(3), Some(0)), (3, 1));
+        assert_eq!(page_bounds(Some(i64::MAX), None), (MAX_PAGE, 10));
+        assert_eq!(total_pages(0, 10), 0);
+        assert_eq!(total_pages(21, 10), 3);
+    }
+
+    fn tag(name: &str, post_count: i64) -> BlogTag {
+        BlogTag {
+            id: 0,
+            name: name.to_string(),
+            slug: slugify(name),
+            created_at: None,
+            updated_at: None,
+            categories: None,
+            tags: None,
+            post_count: Some(post_count),
+        }
+    }
+
+    #[test]
+    fn test_autocomplete() {
+        let tags = vec![tag("Go", 9), tag("Rust", 4), tag("Rust-lang", 1), tag("Ruby", 4), tag("Trust", 20)];
+        let names: Vec<String> = autocomplete(tags.clone(), "RU", 10).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["Rust", "Ruby", "Rust-lang"]);
+        assert_eq!(autocomplete(tags, "ru", 1).len(), 1);
+    }
+}
@@ -0,0 +1,248 @@
+use axum::{
+    extract::{State, Path, Query},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::comments;
+use crate::models::{BlogComment, CommentNode};
+use crate::error::AppError;
+use crate::state::AppState;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CommentPayload {
+    pub name: String,
+    pub email: String,
+    pub body: String,
+    /// Comment being replied to
+    pub parent_id: Option<i32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CommentSubmitted {
+    pub success: bool,
+    pub id: i32,
+    /// Always `pending`; the comment shows up once approved
+    pub status: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/posts/{slug}/comments",
+    params(
+        ("slug" = String, Path, description = "Post slug")
+    ),
+    responses(
+        (status = 200, description = "Approved comments as a reply tree, oldest first", body = [CommentNode]),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_comments(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<CommentNode>>, AppError> {
+    if let Some(cached) = state.comments_cache.get(&slug).await {
+        return Ok(Json(cached));
+    }
+
+    let post_id = sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM blog_posts WHERE slug = $1 AND published = true AND scheduled = false"
+    )
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    let rows = sqlx::query_as::<_, CommentNode>(
+        "SELECT id, parent_id, author_name, body_html, created_at FROM blog_comments WHERE post_id = $1 AND status = 'approved' ORDER BY created_at ASC, id ASC"
+    )
+    .bind(post_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let tree = comments::build_tree(rows);
+    state.comments_cache.insert(slug, tree.clone()).await;
+    Ok(Json(tree))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/blog/posts/{slug}/comments",
+    params(
+        ("slug" = String, Path, description = "Post slug")
+    ),
+    request_body = CommentPayload,
+    responses(
+        (status = 200, description = "Comment stored for moderation", body = CommentSubmitted),
+        (status = 400, description = "Invalid comment, unknown parent or comments closed"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn submit_comment(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Json(payload): Json<CommentPayload>,
+) -> Result<Json<CommentSubmitted>, AppError> {
+    comments::validate(&payload.name, &payload.email, &payload.body).map_err(AppError::ValidationError)?;
+
+    let (post_id, closed) = sqlx::query_as::<_, (i32, bool)>(
+        "SELECT id, comments_closed FROM blog_posts WHERE slug = $1 AND published = true AND scheduled = false"
+    )
+    .bind(&slug)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    if closed {
+        return Err(AppError::ValidationError("Comments are closed for this post".to_string()));
+    }
+
+    if let Some(parent_id) = payload.parent_id {
+        let parent_ok = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM blog_comments WHERE id = $1 AND post_id = $2 AND status = 'approved')"
+        )
+        .bind(parent_id)
+        .bind(post_id)
+        .fetch_one(&state.pool)
+        .await?;
+
+        if !parent_ok {
+            return Err(AppError::ValidationError("Unknown parent comment".to_string()));
+        }
+    }
+
+    let body = payload.body.trim();
+    let id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO blog_comments (post_id, parent_id, author_name, author_email, body, body_html) VALUES ($1, $2, $3, $4, $5, $6) RETURNING id"
+    )
+    .bind(post_id)
+    .bind(payload.parent_id)
+    .bind(payload.name.trim())
+    .bind(payload.email.trim())
+    .bind(body)
+    .bind(comments::render_markdown_lite(body))
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(CommentSubmitted { success: true, id, status: String::from("pending") }))
+}
+
+// ========== Admin Comment Moderation ==========
+
+const STATUSES: [&str; 4] = ["pending", "approved", "rejected", "spam"];
+
+#[derive(Deserialize, Debug)]
+pub struct ModerationQuery {
+    pub status: Option<String>,
+}
+
+// GET /api/blog/admin/comments?status=pending - Moderation queue, newest first
+pub async fn get_admin_comments(
+    State(state): State<AppState>,
+    Query(query): Query<ModerationQuery>,
+) -> Result<Json<Vec<BlogComment>>, AppError> {
+    let status = query.status.unwrap_or_else(|| String::from("pending"));
+    if !STATUSES.contains(&status.as_str()) {
+        return Err(AppError::ValidationError(format!("Unknown status: {}", status)));
+    }
+
+    let comments = sqlx::query_as::<_, BlogComment>(
+        r#"
+SELECT c.id, c.post_id, c.parent_id, c.author_name, c.author_email, c.body, c.body_html, c.status, c.created_at, c.updated_at, p.slug AS post_slug
+FROM blog_comments c
+JOIN blog_posts p ON p.id = c.post_id
+WHERE c.status = $1
+ORDER BY c.created_at DESC, c.id DESC
+"#
+    )
+    .bind(&status)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(comments))
+}
+
+async fn set_status(state: &AppState, id: i32, status: &str) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("UPDATE blog_comments SET status = $1, updated_at = NOW() WHERE id = $2")
+        .bind(status)
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    state.comments_cache.invalidate_all().await;
+
+    Ok(Json(serde_json::json!({ "success": true, "status": status })))
+}
+
+// POST /api/blog/admin/comments/:id/approve
+pub async fn approve_comment(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    set_status(&state, id, "approved").await
+}
+
+// POST /api/blog/admin/comments/:id/reject
+pub async fn reject_comment(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    set_status(&state, id, "rejected").await
+}
+
+// POST /api/blog/admin/comments/:id/spam
+pub async fn mark_comment_spam(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    set_status(&state, id, "spam").await
+}
+
+// DELETE /api/blog/admin/comments/:id - Replies are deleted with it
+pub async fn delete_comment(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("DELETE FROM blog_comments WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    state.comments_cache.invalidate_all().await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[derive(Deserialize)]
+pub struct CommentsClosedPayload {
+    pub closed: bool,
+}
+
+// PUT /api/blog/admin/posts/:id/comments - Open or close comments on a post
+pub async fn set_comments_closed(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<CommentsClosedPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("UPDATE blog_posts SET comments_closed = $1 WHERE id = $2")
+        .bind(payload.closed)
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    state.invalidate_posts().await;
+
+    Ok(Json(serde_json::json!({ "success": true, "closed": payload.closed })))
+}
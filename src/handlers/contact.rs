@@ -2,6 +2,7 @@ use axum::{extract::{State, Path, Query}, Json};
 use serde::Deserialize;
 use crate::models::{ContactInfo, SocialLink, ContactMessage};
 use crate::error::AppError;
+use crate::ordering::{self, ReorderPayload};
 use crate::state::AppState;
 use utoipa::ToSchema;
 
@@ -41,7 +42,7 @@ pub async fn get_social_links(State(state): State<AppState>) -> Result<Json<Vec<
     }
 
     let links = sqlx::query_as::<_, SocialLink>(
-        "SELECT id, name, url, created_at, updated_at FROM social_links ORDER BY id ASC"
+        "SELECT id, name, url, created_at, updated_at FROM social_links ORDER BY sort_order, id ASC"
     )
     .fetch_all(&state.pool)
     .await?;
@@ -116,7 +117,7 @@ pub async fn create_social(
     State(state): State<AppState>,
     Json(payload): Json<SocialPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    sqlx::query("INSERT INTO social_links (name, url) VALUES ($1, $2)")
+    sqlx::query("INSERT INTO social_links (name, url, sort_order) VALUES ($1, $2, (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM social_links))")
         .bind(&payload.name)
         .bind(&payload.url)
         .execute(&state.pool)
@@ -166,6 +167,18 @@ pub async fn delete_social(
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+// PUT /api/contact/socials/reorder - Set the display order from a complete, ordered id list
+pub async fn reorder_socials(
+    State(state): State<AppState>,
+    Json(payload): Json<ReorderPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    ordering::apply_order(&state.pool, "social_links", &payload.ids).await?;
+
+    state.socials_cache.invalidate(&String::from("socials")).await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
 // Admin: Messages
 pub async fn get_messages(State(state): State<AppState>) -> Result<Json<Vec<ContactMessage>>, AppError> {
     let messages = sqlx::query_as::<_, ContactMessage>(
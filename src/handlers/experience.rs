@@ -1,32 +1,55 @@
-use axum::{extract::{State, Path}, Json};
+use axum::{
+    extract::{State, Path, Query},
+    http::HeaderMap,
+    response::Response,
+    Json,
+};
 use serde::Deserialize;
 use utoipa::ToSchema;
+use crate::i18n::{self, TranslatableKind};
 use crate::models::ExperienceTimeline;
 use crate::error::AppError;
+use crate::ordering::{self, ReorderPayload};
 use crate::state::AppState;
+use super::translations::{self, LangQuery};
 
 #[utoipa::path(
     get,
     path = "/api/experience",
+    params(
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
     responses(
         (status = 200, description = "Get experience timeline", body = [ExperienceTimeline])
     )
 )]
-pub async fn get_experience(State(state): State<AppState>) -> Result<Json<Vec<ExperienceTimeline>>, AppError> {
+pub async fn get_experience(
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let cache_key = String::from("experience");
-    
-    if let Some(cached) = state.experience_cache.get(&cache_key).await {
-        return Ok(Json(cached));
-    }
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
 
-    let exp = sqlx::query_as::<_, ExperienceTimeline>(
-        "SELECT id, title, company, period, description, skills, categories, tags, details, created_at, updated_at FROM experience_timeline /* force_new_plan */ ORDER BY id DESC"
-    )
-    .fetch_all(&state.pool)
-    .await?;
+    let mut exp = match state.experience_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => {
+            let exp = sqlx::query_as::<_, ExperienceTimeline>(
+                "SELECT id, title, company, period, description, skills, categories, tags, details, created_at, updated_at FROM experience_timeline /* force_new_plan */ ORDER BY sort_order, id DESC"
+            )
+            .fetch_all(&state.pool)
+            .await?;
 
-    state.experience_cache.insert(cache_key, exp.clone()).await;
-    Ok(Json(exp))
+            state.experience_cache.insert(cache_key, exp.clone()).await;
+            exp
+        }
+    };
+
+    let translations = translations::load(&state, TranslatableKind::Experience).await?;
+    for item in &mut exp {
+        i18n::localize(item, &translations, &locale, &state.site);
+    }
+    Ok(i18n::localized_json(exp, &locale))
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -49,7 +72,7 @@ pub async fn create_timeline(
     Json(payload): Json<TimelinePayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     sqlx::query(
-        "INSERT INTO experience_timeline (title, company, period, description, skills, categories, tags, details) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        "INSERT INTO experience_timeline (title, company, period, description, skills, categories, tags, details, sort_order) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, (SELECT COALESCE(MIN(sort_order), 0) - 1 FROM experience_timeline))"
     )
     .bind(&payload.title)
     .bind(&payload.company)
@@ -134,3 +157,15 @@ pub async fn delete_timeline(
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
+
+// PUT /api/experience/timeline/reorder - Set the display order from a complete, ordered id list
+pub async fn reorder_timeline(
+    State(state): State<AppState>,
+    Json(payload): Json<ReorderPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    ordering::apply_order(&state.pool, "experience_timeline", &payload.ids).await?;
+
+    state.experience_cache.invalidate(&String::from("experience")).await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
@@ -0,0 +1,43 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use crate::export::{self, ExportTerm};
+use crate::models::BlogPost;
+use crate::error::AppError;
+use crate::state::AppState;
+use super::blog::POST_SELECT;
+
+// GET /api/blog/admin/export - Every post (drafts and scheduled included) as a zip of
+// Markdown files with front matter plus manifest.json. The zip can be re-imported as-is.
+pub async fn export_posts(State(state): State<AppState>) -> Result<Response, AppError> {
+    let posts = sqlx::query_as::<_, BlogPost>(&format!("{} ORDER BY p.id", POST_SELECT))
+        .fetch_all(&state.pool)
+        .await?;
+    let categories = sqlx::query_as::<_, ExportTerm>("SELECT name, slug FROM blog_categories ORDER BY slug")
+        .fetch_all(&state.pool)
+        .await?;
+    let tags = sqlx::query_as::<_, ExportTerm>("SELECT name, slug FROM blog_tags ORDER BY slug")
+        .fetch_all(&state.pool)
+        .await?;
+
+    let authors = sqlx::query_as::<_, ExportTerm>("SELECT name, slug FROM blog_authors ORDER BY slug")
+        .fetch_all(&state.pool)
+        .await?;
+
+    let now = Utc::now();
+    let bytes = export::build_bundle(&posts, categories, tags, authors, &state.site, now)?;
+    let disposition = format!("attachment; filename=\"blog-export-{}.zip\"", now.format("%Y%m%d-%H%M%S"));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        bytes,
+    )
+        .into_response())
+}
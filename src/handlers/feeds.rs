@@ -0,0 +1,432 @@
+use axum::{
+    extract::{State, Path, Query},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use crate::error::AppError;
+use crate::feeds::{self, CachedFeed, FeedChannel, JsonFeed, JsonFeedAuthor};
+use crate::models::BlogPost;
+use crate::state::AppState;
+use super::blog::{clamp_page, POST_SELECT};
+
+const RSS_CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+const JSON_FEED_CONTENT_TYPE: &str = "application/feed+json; charset=utf-8";
+
+/// Feed readers poll often; let them and any CDN reuse a response for 15 minutes.
+const FEED_MAX_AGE_SECS: u32 = 900;
+
+fn http_date(date: NaiveDateTime) -> String {
+    date.and_utc().format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Wrap a rendered body with its validators.
+pub(crate) fn cached_feed(body: String, last_modified: Option<NaiveDateTime>) -> CachedFeed {
+    let digest = format!("{:x}", Sha256::digest(body.as_bytes()));
+    let etag = format!("\"{}\"", &digest[..16]);
+    CachedFeed { body, etag, last_modified }
+}
+
+/// Serve a cached body with `Cache-Control`, `ETag` and `Last-Modified`,
+/// answering `If-None-Match` / `If-Modified-Since` with 304.
+pub(crate) fn conditional_response(request: &HeaderMap, content_type: &'static str, feed: CachedFeed) -> Response {
+    let etag_matches = request
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').any(|tag| tag.trim() == feed.etag || tag.trim() == "*"))
+        .unwrap_or(false);
+
+    let not_modified_since = match (
+        request.get(header::IF_MODIFIED_SINCE).and_then(|v| v.to_str().ok()),
+        feed.last_modified,
+    ) {
+        (Some(since), Some(modified)) => chrono::DateTime::parse_from_rfc2822(since)
+            .map(|since| modified.and_utc().timestamp() <= since.timestamp())
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_str(&format!("public, max-age={}", FEED_MAX_AGE_SECS)).unwrap(),
+    );
+    if let Ok(etag) = HeaderValue::from_str(&feed.etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    if let Some(modified) = feed.last_modified {
+        if let Ok(value) = HeaderValue::from_str(&http_date(modified)) {
+            headers.insert(header::LAST_MODIFIED, value);
+        }
+    }
+
+    // If-None-Match takes precedence over If-Modified-Since when both are sent.
+    let not_modified = if request.contains_key(header::IF_NONE_MATCH) {
+        etag_matches
+    } else {
+        not_modified_since
+    };
+
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    (headers, feed.body).into_response()
+}
+
+#[derive(Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    fn key(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "rss",
+            FeedFormat::Atom => "atom",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => RSS_CONTENT_TYPE,
+            FeedFormat::Atom => ATOM_CONTENT_TYPE,
+        }
+    }
+}
+
+pub(crate) enum FeedScope {
+    All,
+    Category(String),
+    Tag(String),
+}
+
+impl FeedScope {
+    pub(crate) fn cache_key(&self, format: &str) -> String {
+        match self {
+            FeedScope::All => format.to_string(),
+            FeedScope::Category(slug) => format!("{}:category:{}", format, slug),
+            FeedScope::Tag(slug) => format!("{}:tag:{}", format, slug),
+        }
+    }
+}
+
+/// Published posts for a feed scope, with the channel metadata describing it.
+pub(crate) struct ScopePosts {
+    pub title: String,
+    pub description: String,
+    /// Frontend page listing the same posts
+    pub link: String,
+    pub posts: Vec<BlogPost>,
+}
+
+/// Latest published posts for the scope. Unknown category or tag slugs are a 404.
+pub(crate) async fn load_scope(
+    state: &AppState,
+    scope: &FeedScope,
+    limit: i64,
+    offset: i64,
+) -> Result<ScopePosts, AppError> {
+    let site = &state.site;
+    let (title, description, link, category, tag) = match scope {
+        FeedScope::All => (
+            site.title.clone(),
+            site.description.clone(),
+            format!("{}{}", site.base_url, site.blog_path),
+            None,
+            None,
+        ),
+        FeedScope::Category(slug) => {
+            let (name, description) = sqlx::query_as::<_, (String, Option<String>)>(
+                "SELECT name, description FROM blog_categories WHERE slug = $1"
+            )
+            .bind(slug)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(AppError::NotFound)?;
+            (
+                format!("{} - {}", site.title, name),
+                description.unwrap_or_else(|| format!("Posts in {}", name)),
+                site.category_url(slug),
+                Some(slug.as_str()),
+                None,
+            )
+        }
+        FeedScope::Tag(slug) => {
+            let name = sqlx::query_scalar::<_, String>("SELECT name FROM blog_tags WHERE slug = $1")
+                .bind(slug)
+                .fetch_optional(&state.pool)
+                .await?
+                .ok_or(AppError::NotFound)?;
+            (
+                format!("{} - {}", site.title, name),
+                format!("Posts tagged {}", name),
+                site.tag_url(slug),
+                None,
+                Some(slug.as_str()),
+            )
+        }
+    };
+
+    let posts = sqlx::query_as::<_, BlogPost>(&format!(
+        r#"{}
+WHERE p.published = true AND p.scheduled = false
+  AND ($1::text IS NULL OR EXISTS (
+        SELECT 1 FROM blog_post_categories pc JOIN blog_categories c ON c.id = pc.category_id
+        WHERE pc.post_id = p.id AND c.slug = $1))
+  AND ($2::text IS NULL OR EXISTS (
+        SELECT 1 FROM blog_post_tags pt JOIN blog_tags t ON t.id = pt.tag_id
+        WHERE pt.post_id = p.id AND t.slug = $2))
+ORDER BY p.published_at DESC, p.id DESC
+LIMIT $3 OFFSET $4"#,
+        POST_SELECT
+    ))
+    .bind(category)
+    .bind(tag)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(ScopePosts { title, description, link, posts })
+}
+
+fn scope_path(scope: &FeedScope, file_name: &str) -> String {
+    match scope {
+        FeedScope::All => format!("/{}", file_name),
+        FeedScope::Category(slug) => format!("/api/blog/categories/{}/{}", slug, file_name),
+        FeedScope::Tag(slug) => format!("/api/blog/tags/{}/{}", slug, file_name),
+    }
+}
+
+async fn xml_feed(state: AppState, request: HeaderMap, format: FeedFormat, scope: FeedScope) -> Result<Response, AppError> {
+    let cache_key = scope.cache_key(format.key());
+
+    if let Some(cached) = state.feeds_cache.get(&cache_key).await {
+        return Ok(conditional_response(&request, format.content_type(), cached));
+    }
+
+    let scoped = load_scope(&state, &scope, state.site.feed_limit, 0).await?;
+    let channel = FeedChannel {
+        title: scoped.title,
+        description: scoped.description,
+        link: scoped.link,
+        self_url: state.site.api_path_url(&scope_path(&scope, format.file_name())),
+    };
+
+    let body = match format {
+        FeedFormat::Rss => feeds::render_rss(&channel, &scoped.posts, &state.site),
+        FeedFormat::Atom => feeds::render_atom(&channel, &scoped.posts, &state.site),
+    };
+    let feed = cached_feed(body, feeds::last_modified(&scoped.posts));
+
+    state.feeds_cache.insert(cache_key, feed.clone()).await;
+    Ok(conditional_response(&request, format.content_type(), feed))
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed.xml",
+    responses(
+        (status = 200, description = "RSS 2.0 feed of published posts", body = String, content_type = "application/rss+xml"),
+        (status = 304, description = "Not modified")
+    )
+)]
+pub async fn rss_feed(State(state): State<AppState>, headers: HeaderMap) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Rss, FeedScope::All).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/atom.xml",
+    responses(
+        (status = 200, description = "Atom feed of published posts", body = String, content_type = "application/atom+xml"),
+        (status = 304, description = "Not modified")
+    )
+)]
+pub async fn atom_feed(State(state): State<AppState>, headers: HeaderMap) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Atom, FeedScope::All).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/categories/{slug}/feed.xml",
+    params(
+        ("slug" = String, Path, description = "Category slug")
+    ),
+    responses(
+        (status = 200, description = "RSS 2.0 feed of a category", body = String, content_type = "application/rss+xml"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn category_rss_feed(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Rss, FeedScope::Category(slug)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/categories/{slug}/atom.xml",
+    params(
+        ("slug" = String, Path, description = "Category slug")
+    ),
+    responses(
+        (status = 200, description = "Atom feed of a category", body = String, content_type = "application/atom+xml"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn category_atom_feed(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Atom, FeedScope::Category(slug)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/tags/{slug}/feed.xml",
+    params(
+        ("slug" = String, Path, description = "Tag slug")
+    ),
+    responses(
+        (status = 200, description = "RSS 2.0 feed of a tag", body = String, content_type = "application/rss+xml"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn tag_rss_feed(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Rss, FeedScope::Tag(slug)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/tags/{slug}/atom.xml",
+    params(
+        ("slug" = String, Path, description = "Tag slug")
+    ),
+    responses(
+        (status = 200, description = "Atom feed of a tag", body = String, content_type = "application/atom+xml"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn tag_atom_feed(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    xml_feed(state, headers, FeedFormat::Atom, FeedScope::Tag(slug)).await
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JsonFeedQuery {
+    pub page: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed.json",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number, starting at 1")
+    ),
+    responses(
+        (status = 200, description = "JSON Feed 1.1 of published posts", body = String, content_type = "application/feed+json"),
+        (status = 304, description = "Not modified")
+    )
+)]
+pub async fn json_feed(
+    State(state): State<AppState>,
+    Query(query): Query<JsonFeedQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let page = clamp_page(query.page);
+    let cache_key = format!("json:{}", page);
+
+    if let Some(cached) = state.feeds_cache.get(&cache_key).await {
+        return Ok(conditional_response(&headers, JSON_FEED_CONTENT_TYPE, cached));
+    }
+
+    let site = &state.site;
+    let per_page = site.feed_limit;
+
+    // One extra row tells us whether there is a next page.
+    let mut scoped = load_scope(&state, &FeedScope::All, per_page + 1, (page - 1) * per_page).await?;
+    let has_next = scoped.posts.len() as i64 > per_page;
+    scoped.posts.truncate(per_page as usize);
+
+    let feed_url = site.api_path_url("/feed.json");
+    let document = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: scoped.title,
+        home_page_url: scoped.link,
+        next_url: has_next.then(|| format!("{}?page={}", feed_url, page + 1)),
+        feed_url,
+        description: scoped.description,
+        language: site.language.clone(),
+        authors: vec![JsonFeedAuthor { name: site.author.clone() }],
+        items: scoped.posts.iter().map(|post| feeds::json_feed_item(post, site)).collect(),
+    };
+
+    let body = serde_json::to_string(&document).map_err(|e| AppError::InternalError(e.into()))?;
+    let feed = cached_feed(body, feeds::last_modified(&scoped.posts));
+
+    state.feeds_cache.insert(cache_key, feed.clone()).await;
+    Ok(conditional_response(&headers, JSON_FEED_CONTENT_TYPE, feed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed() -> CachedFeed {
+        let modified = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_hms_opt(3, 4, 5).unwrap();
+        cached_feed("<rss/>".to_string(), Some(modified))
+    }
+
+    #[test]
+    fn test_conditional_response_sets_cache_headers() {
+        let response = conditional_response(&HeaderMap::new(), RSS_CONTENT_TYPE, feed());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], RSS_CONTENT_TYPE);
+        assert_eq!(response.headers()[header::LAST_MODIFIED], "Tue, 02 Jan 2024 03:04:05 GMT");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=900");
+        assert!(response.headers().contains_key(header::ETAG));
+    }
+
+    #[test]
+    fn test_conditional_response_etag_match() {
+        let feed = feed();
+        let mut request = HeaderMap::new();
+        request.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&feed.etag).unwrap());
+        let response = conditional_response(&request, RSS_CONTENT_TYPE, feed);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[test]
+    fn test_conditional_response_if_modified_since() {
+        let mut request = HeaderMap::new();
+        request.insert(header::IF_MODIFIED_SINCE, HeaderValue::from_static("Tue, 02 Jan 2024 03:04:05 GMT"));
+        assert_eq!(conditional_response(&request, RSS_CONTENT_TYPE, feed()).status(), StatusCode::NOT_MODIFIED);
+
+        request.insert(header::IF_MODIFIED_SINCE, HeaderValue::from_static("Mon, 01 Jan 2024 00:00:00 GMT"));
+        assert_eq!(conditional_response(&request, RSS_CONTENT_TYPE, feed()).status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Multipart, State, Query},
+    Json,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use crate::import::{self, ImportDoc};
+use crate::models::BlogPostRevision;
+use crate::error::AppError;
+use crate::state::AppState;
+use super::blog::slugify;
+use super::authors;
+use super::revisions::{self, FieldDiff};
+
+pub const MAX_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+#[derive(Deserialize, Debug)]
+pub struct ImportQuery {
+    /// Defaults to true: report what would change without writing anything
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImportFileReport {
+    pub file: String,
+    pub slug: Option<String>,
+    /// `created`, `updated`, `unchanged` or `error`
+    pub action: &'static str,
+    pub post_id: Option<i32>,
+    pub error: Option<String>,
+    /// Per-field diff against the post currently stored under the slug
+    pub changes: Vec<FieldDiff>,
+}
+
+impl ImportFileReport {
+    fn error(file: &str, slug: Option<String>, error: String) -> Self {
+        Self { file: file.to_string(), slug, action: "error", post_id: None, error: Some(error), changes: Vec::new() }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// False on a dry run, or when any file had an error (nothing is written then)
+    pub committed: bool,
+    pub files: Vec<ImportFileReport>,
+    pub created_categories: Vec<String>,
+    pub created_tags: Vec<String>,
+}
+
+/// Terms created while importing; shared across files so each is created once.
+#[derive(Default)]
+struct CreatedTerms {
+    categories: Vec<String>,
+    tags: Vec<String>,
+}
+
+/// Ids for category names or slugs, creating the missing ones.
+async fn resolve_categories(conn: &mut PgConnection, values: &[String], created: &mut CreatedTerms) -> Result<Vec<i32>, AppError> {
+    let mut ids = Vec::new();
+    for value in values {
+        let slug = slugify(value);
+        let existing = sqlx::query_scalar::<_, i32>("SELECT id FROM blog_categories WHERE slug = $1")
+            .bind(&slug)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                created.categories.push(slug.clone());
+                sqlx::query_scalar::<_, i32>("INSERT INTO blog_categories (name, slug) VALUES ($1, $2) RETURNING id")
+                    .bind(value)
+                    .bind(&slug)
+                    .fetch_one(&mut *conn)
+                    .await?
+            }
+        };
+        ids.push(id);
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Ids for tag names or slugs, following renames and merges, creating the missing ones.
+async fn resolve_tags(conn: &mut PgConnection, values: &[String], created: &mut CreatedTerms) -> Result<Vec<i32>, AppError> {
+    let mut ids = Vec::new();
+    for value in values {
+        let slug = slugify(value);
+        let existing = sqlx::query_scalar::<_, i32>(
+            "SELECT id FROM blog_tags WHERE slug = $1 UNION ALL SELECT tag_id FROM blog_tag_slug_history WHERE slug = $1 LIMIT 1"
+        )
+        .bind(&slug)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                created.tags.push(slug.clone());
+                sqlx::query_scalar::<_, i32>("INSERT INTO blog_tags (name, slug) VALUES ($1, $2) RETURNING id")
+                    .bind(value)
+                    .bind(&slug)
+                    .fetch_one(&mut *conn)
+                    .await?
+            }
+        };
+        ids.push(id);
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// The stored post under `slug`, shaped as a revision for diffing.
+async fn current_revision(conn: &mut PgConnection, slug: &str) -> Result<Option<BlogPostRevision>, AppError> {
+    let current = sqlx::query_as::<_, BlogPostRevision>(
+        r#"
+SELECT 0 AS id, p.id AS post_id, p.title, p.excerpt, p.content, p.content_markdown, p.author, p.meta_title, p.meta_description, p.meta_keywords,
+    ARRAY(SELECT category_id FROM blog_post_categories WHERE post_id = p.id ORDER BY category_id) AS category_ids,
+    ARRAY(SELECT tag_id FROM blog_post_tags WHERE post_id = p.id ORDER BY tag_id) AS tag_ids,
+    ARRAY(SELECT author_id FROM blog_post_authors WHERE post_id = p.id ORDER BY position, author_id) AS author_ids,
+    p.updated_at AS created_at
+FROM blog_posts p
+WHERE p.slug = $1
+FOR UPDATE OF p
+"#
+    )
+    .bind(slug)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(current)
+}
+
+fn as_revision(
+    doc: &ImportDoc,
+    post_id: i32,
+    category_ids: Vec<i32>,
+    tag_ids: Vec<i32>,
+    author_ids: Vec<i32>,
+    byline: Option<String>,
+) -> BlogPostRevision {
+    BlogPostRevision {
+        id: 0,
+        post_id,
+        title: doc.title.clone(),
+        excerpt: doc.excerpt.clone(),
+        content: doc.content.clone(),
+        content_markdown: Some(doc.content_markdown.clone()),
+        author: byline,
+        meta_title: doc.meta_title.clone(),
+        meta_description: doc.meta_description.clone(),
+        meta_keywords: doc.meta_keywords.clone(),
+        category_ids,
+        tag_ids,
+        author_ids,
+        created_at: None,
+    }
+}
+
+fn empty_revision() -> BlogPostRevision {
+    BlogPostRevision {
+        id: 0,
+        post_id: 0,
+        title: String::new(),
+        excerpt: String::new(),
+        content: String::new(),
+        content_markdown: None,
+        author: None,
+        meta_title: None,
+        meta_description: None,
+        meta_keywords: None,
+        category_ids: Vec::new(),
+        tag_ids: Vec::new(),
+        author_ids: Vec::new(),
+        created_at: None,
+    }
+}
+
+/// Publishing fields that revisions don't track, as `(field, text)` pairs.
+fn publishing_fields(
+    published: bool,
+    published_at: Option<NaiveDateTime>,
+    featured_image: Option<&str>,
+    reading_time: i32,
+) -> [(&'static str, String); 4] {
+    [
+        ("published", format!("{}\n", published)),
+        ("published_at", published_at.map(|d| format!("{}\n", d)).unwrap_or_default()),
+        ("featured_image", featured_image.map(|i| format!("{}\n", i)).unwrap_or_default()),
+        ("reading_time", format!("{}\n", reading_time)),
+    ]
+}
+
+/// Changes to the publishing fields. A document without a date keeps the stored one.
+async fn publishing_changes(conn: &mut PgConnection, post_id: Option<i32>, doc: &ImportDoc) -> Result<Vec<FieldDiff>, AppError> {
+    let stored = match post_id {
+        Some(id) => {
+            sqlx::query_as::<_, (Option<bool>, Option<NaiveDateTime>, Option<String>, Option<i32>)>(
+                "SELECT published, published_at, featured_image, reading_time FROM blog_posts WHERE id = $1"
+            )
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        None => None,
+    };
+
+    let old = match &stored {
+        Some((published, published_at, image, minutes)) => {
+            publishing_fields(published.unwrap_or(false), *published_at, image.as_deref(), minutes.unwrap_or(1))
+        }
+        None => publishing_fields(false, None, None, 0).map(|(field, _)| (field, String::new())),
+    };
+    let published_at = doc.published_at.or(stored.as_ref().and_then(|s| s.1));
+    let new = publishing_fields(doc.published, published_at, doc.featured_image.as_deref(), doc.reading_time);
+
+    Ok(old
+        .into_iter()
+        .zip(new)
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| revisions::diff_field(field, &old, &new))
+        .collect())
+}
+
+/// Create or update the post for one document and report what changed.
+async fn import_doc(conn: &mut PgConnection, file: &str, doc: &ImportDoc, created: &mut CreatedTerms) -> Result<ImportFileReport, AppError> {
+    let category_ids = resolve_categories(conn, &doc.categories, created).await?;
+    let tag_ids = resolve_tags(conn, &doc.tags, created).await?;
+    let author_ids = authors::resolve_authors(conn, &doc.authors).await?;
+    let byline = authors::byline(conn, &author_ids).await?;
+    let current = current_revision(conn, &doc.slug).await?;
+
+    let post_id = current.as_ref().map(|c| c.post_id).unwrap_or(0);
+    let mut changes = revisions::diff_revisions(
+        current.as_ref().unwrap_or(&empty_revision()),
+        &as_revision(doc, post_id, category_ids.clone(), tag_ids.clone(), author_ids.clone(), byline.clone()),
+    );
+    changes.extend(publishing_changes(conn, current.as_ref().map(|c| c.post_id), doc).await?);
+
+    let (post_id, action) = match current {
+        Some(_) if changes.is_empty() => (post_id, "unchanged"),
+        Some(_) => {
+            revisions::snapshot_post(&mut *conn, post_id).await?;
+            sqlx::query(
+                "UPDATE blog_posts SET title = $1, excerpt = $2, content = $3, content_markdown = $4, featured_image = $5, author = $6, published = $7, published_at = COALESCE($8, published_at), scheduled = $7 AND COALESCE($8, published_at) > NOW(), reading_time = $9, meta_title = $10, meta_description = $11, meta_keywords = $12, updated_at = NOW() WHERE id = $13"
+            )
+            .bind(&doc.title)
+            .bind(&doc.excerpt)
+            .bind(&doc.content)
+            .bind(&doc.content_markdown)
+            .bind(&doc.featured_image)
+            .bind(&byline)
+            .bind(doc.published)
+            .bind(doc.published_at)
+            .bind(doc.reading_time)
+            .bind(&doc.meta_title)
+            .bind(&doc.meta_description)
+            .bind(&doc.meta_keywords)
+            .bind(post_id)
+            .execute(&mut *conn)
+            .await?;
+            (post_id, "updated")
+        }
+        None => {
+            // A new post taking over a retired slug stops it redirecting elsewhere.
+            sqlx::query("DELETE FROM blog_slug_history WHERE slug = $1")
+                .bind(&doc.slug)
+                .execute(&mut *conn)
+                .await?;
+            let post_id = sqlx::query_scalar::<_, i32>(
+                "INSERT INTO blog_posts (title, slug, excerpt, content, content_markdown, featured_image, author, published, published_at, scheduled, reading_time, meta_title, meta_description, meta_keywords) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, NOW()), $8 AND COALESCE($9, NOW()) > NOW(), $10, $11, $12, $13) RETURNING id"
+            )
+            .bind(&doc.title)
+            .bind(&doc.slug)
+            .bind(&doc.excerpt)
+            .bind(&doc.content)
+            .bind(&doc.content_markdown)
+            .bind(&doc.featured_image)
+            .bind(&byline)
+            .bind(doc.published)
+            .bind(doc.published_at)
+            .bind(doc.reading_time)
+            .bind(&doc.meta_title)
+            .bind(&doc.meta_description)
+            .bind(&doc.meta_keywords)
+            .fetch_one(&mut *conn)
+            .await?;
+            (post_id, "created")
+        }
+    };
+
+    if action != "unchanged" {
+        sqlx::query("DELETE FROM blog_post_categories WHERE post_id = $1").bind(post_id).execute(&mut *conn).await?;
+        sqlx::query("INSERT INTO blog_post_categories (post_id, category_id) SELECT $1, UNNEST($2::int[])")
+            .bind(post_id)
+            .bind(&category_ids)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query("DELETE FROM blog_post_tags WHERE post_id = $1").bind(post_id).execute(&mut *conn).await?;
+        sqlx::query("INSERT INTO blog_post_tags (post_id, tag_id) SELECT $1, UNNEST($2::int[])")
+            .bind(post_id)
+            .bind(&tag_ids)
+            .execute(&mut *conn)
+            .await?;
+        authors::sync_authors(conn, post_id, &author_ids).await?;
+    }
+
+    Ok(ImportFileReport {
+        file: file.to_string(),
+        slug: Some(doc.slug.clone()),
+        action,
+        post_id: Some(post_id),
+        error: None,
+        changes,
+    })
+}
+
+// POST /api/blog/admin/import?dry_run=false - Multipart `file`: a zip of Markdown files
+// with YAML (`---`) or TOML (`+++`) front matter, or a single .md file. Posts are
+// upserted by slug and missing categories/tags are created, all in one transaction.
+// Without `dry_run=false`, or if any file fails, nothing is written and the report
+// shows what would have changed.
+pub async fn import_posts(
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    mut multipart: Multipart,
+) -> Result<Json<ImportReport>, AppError> {
+    let dry_run = query.dry_run.unwrap_or(true);
+
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| AppError::UploadError(e.to_string()))? {
+        if field.name() == Some("file") {
+            let name = field.file_name().unwrap_or("upload.md").to_string();
+            let bytes = field.bytes().await.map_err(|e| AppError::UploadError(e.to_string()))?;
+            upload = Some((name, bytes.to_vec()));
+            break;
+        }
+    }
+
+    let (name, bytes) = upload.ok_or_else(|| AppError::ValidationError("No file uploaded".to_string()))?;
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(AppError::ValidationError(format!("Upload too large. Max {}MB.", MAX_UPLOAD_BYTES / 1024 / 1024)));
+    }
+    let files = import::read_upload(&name, bytes).map_err(AppError::ValidationError)?;
+
+    let mut tx = state.pool.begin().await?;
+    let mut created = CreatedTerms::default();
+    let mut reports = Vec::new();
+    let mut seen_slugs = HashSet::new();
+
+    for file in files {
+        let doc = file.text.and_then(|text| import::build_doc(&text));
+        let report = match doc {
+            Err(error) => ImportFileReport::error(&file.name, None, error),
+            Ok(doc) if !seen_slugs.insert(doc.slug.clone()) => {
+                ImportFileReport::error(&file.name, Some(doc.slug), "Another file in the upload has the same slug".to_string())
+            }
+            Ok(doc) => import_doc(&mut tx, &file.name, &doc, &mut created).await?,
+        };
+        reports.push(report);
+    }
+
+    if dry_run {
+        // Ids handed out inside the rolled-back transaction don't exist.
+        for report in reports.iter_mut().filter(|r| r.action == "created") {
+            report.post_id = None;
+        }
+    }
+
+    let has_errors = reports.iter().any(|r| r.action == "error");
+    let committed = !dry_run && !has_errors;
+    if committed {
+        tx.commit().await?;
+        state.invalidate_posts().await;
+    } else {
+        tx.rollback().await?;
+    }
+
+    Ok(Json(ImportReport {
+        dry_run,
+        committed,
+        files: reports,
+        created_categories: created.categories,
+        created_tags: created.tags,
+    }))
+}
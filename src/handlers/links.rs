@@ -0,0 +1,143 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json,
+};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use crate::error::AppError;
+use crate::links::{self, LinkChecker};
+use crate::state::AppState;
+
+#[derive(Serialize, sqlx::FromRow, Debug, Clone)]
+pub struct BrokenLink {
+    #[serde(skip)]
+    pub entity: String,
+    #[serde(skip)]
+    pub entity_id: i32,
+    #[serde(skip)]
+    pub title: Option<String>,
+    pub url: String,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub final_url: Option<String>,
+    pub checked_at: NaiveDateTime,
+}
+
+/// Broken links of one post, project or social link.
+#[derive(Serialize, Debug)]
+pub struct EntityLinks {
+    /// `post`, `project` or `social`
+    pub entity: String,
+    pub entity_id: i32,
+    /// Post or project title, or social link name
+    pub title: Option<String>,
+    pub links: Vec<BrokenLink>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LinkReport {
+    /// When the last run finished writing, if there has been one
+    pub checked_at: Option<NaiveDateTime>,
+    pub total_links: i64,
+    pub broken_links: i64,
+    pub entities: Vec<EntityLinks>,
+}
+
+/// Group rows ordered by entity into one entry per entity.
+fn group_by_entity(rows: Vec<BrokenLink>) -> Vec<EntityLinks> {
+    let mut groups: Vec<EntityLinks> = Vec::new();
+    for row in rows {
+        match groups.last_mut() {
+            Some(group) if group.entity == row.entity && group.entity_id == row.entity_id => group.links.push(row),
+            _ => groups.push(EntityLinks {
+                entity: row.entity.clone(),
+                entity_id: row.entity_id,
+                title: row.title.clone(),
+                links: vec![row],
+            }),
+        }
+    }
+    groups
+}
+
+// GET /api/admin/links - Broken links from the last check, grouped by the item they're in
+pub async fn get_link_report(State(state): State<AppState>) -> Result<Json<LinkReport>, AppError> {
+    let (checked_at, total_links, broken_links) = sqlx::query_as::<_, (Option<NaiveDateTime>, i64, i64)>(
+        "SELECT MAX(checked_at), COUNT(*), COUNT(*) FILTER (WHERE broken) FROM link_checks"
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let rows = sqlx::query_as::<_, BrokenLink>(
+        r#"
+SELECT l.entity, l.entity_id, COALESCE(p.title, pr.title, s.name) AS title,
+    l.url, l.status_code, l.error, l.final_url, l.checked_at
+FROM link_checks l
+LEFT JOIN blog_posts p ON l.entity = 'post' AND p.id = l.entity_id
+LEFT JOIN projects pr ON l.entity = 'project' AND pr.id = l.entity_id
+LEFT JOIN social_links s ON l.entity = 'social' AND s.id = l.entity_id
+WHERE l.broken
+ORDER BY l.entity, l.entity_id, l.url
+"#
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(LinkReport { checked_at, total_links, broken_links, entities: group_by_entity(rows) }))
+}
+
+// POST /api/admin/links/check - Start a check now; the report updates when it finishes
+pub async fn start_link_check(State(state): State<AppState>) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    if links::is_running() {
+        return Ok((StatusCode::ACCEPTED, Json(serde_json::json!({ "success": true, "already_running": true }))));
+    }
+    let checker = LinkChecker::new(&state.link_check).map_err(anyhow::Error::from)?;
+
+    tokio::spawn(async move {
+        match links::check_links(&state.pool, &checker).await {
+            Ok(Some(summary)) => tracing::info!("Checked {} links, {} broken", summary.checked, summary.broken),
+            Ok(None) => tracing::info!("Link check already running"),
+            Err(e) => tracing::error!("Failed to check links: {:?}", e),
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(serde_json::json!({ "success": true, "already_running": false }))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(entity: &str, entity_id: i32, url: &str) -> BrokenLink {
+        BrokenLink {
+            entity: entity.to_string(),
+            entity_id,
+            title: Some(format!("{} {}", entity, entity_id)),
+            url: url.to_string(),
+            status_code: Some(404),
+            error: None,
+            final_url: None,
+            checked_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_entity() {
+        let groups = group_by_entity(vec![
+            row("post", 1, "https://a.example"),
+            row("post", 1, "https://b.example"),
+            row("post", 2, "https://a.example"),
+            row("social", 1, "https://c.example"),
+        ]);
+
+        let summary: Vec<(&str, i32, usize)> =
+            groups.iter().map(|g| (g.entity.as_str(), g.entity_id, g.links.len())).collect();
+        assert_eq!(summary, [("post", 1, 2), ("post", 2, 1), ("social", 1, 1)]);
+        assert_eq!(groups[0].title.as_deref(), Some("post 1"));
+
+        let json = serde_json::to_value(&groups[0]).unwrap();
+        assert!(json["links"][0].get("entity").is_none());
+        assert_eq!(json["links"][1]["url"], "https://b.example");
+    }
+}
@@ -4,6 +4,18 @@ pub mod experience;
 pub mod projects;
 pub mod contact;
 pub mod blog;
+pub mod archive;
+pub mod authors;
+pub mod revisions;
+pub mod series;
+pub mod tags;
+pub mod comments;
+pub mod translations;
+pub mod export;
+pub mod feeds;
+pub mod import;
+pub mod links;
+pub mod sitemap;
 pub mod upload;
 pub mod admin;
 pub mod health;
@@ -1,27 +1,49 @@
-use axum::{extract::{State, Path, Query}, Json};
+use std::collections::HashSet;
+
+use axum::{
+    extract::{State, Path, Query},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::Deserialize;
-use crate::models::Project;
+use chrono::NaiveDate;
+use crate::i18n::{self, TranslatableKind};
+use crate::import;
+use crate::models::{Project, ProjectSummary};
 use crate::error::AppError;
+use crate::ordering::{self, ReorderPayload};
 use crate::state::AppState;
+use super::blog::{next_free_slug, slugify};
+use super::translations::{self, LangQuery};
+
+const PROJECT_COLUMNS: &str = "id, slug, title, description, image, skills, link, is_active, role, team_size, start_date, end_date, case_study, created_at, updated_at";
 
 #[derive(Deserialize, Debug)]
 pub struct ProjectQuery {
     pub admin: Option<String>,
+    pub lang: Option<String>,
 }
 
 
 #[utoipa::path(
     get,
     path = "/api/projects",
+    params(
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
     responses(
-        (status = 200, description = "Get all projects", body = [Project])
+        (status = 200, description = "Get all projects, without their case studies", body = [ProjectSummary])
     )
 )]
 pub async fn get_projects(
     State(state): State<AppState>,
     query: Option<Query<ProjectQuery>>,
-) -> Result<Json<Vec<Project>>, AppError> {
-    let is_admin = query.and_then(|q| q.0.admin).map(|a| a == "true").unwrap_or(false);
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let query = query.map(|q| q.0);
+    let is_admin = query.as_ref().and_then(|q| q.admin.as_deref()).map(|a| a == "true").unwrap_or(false);
+    let lang = query.and_then(|q| q.lang);
     
     let cache_key = if is_admin {
         String::from("projects_admin")
@@ -29,22 +51,79 @@ pub async fn get_projects(
         String::from("projects")
     };
 
-    if let Some(cached) = state.projects_cache.get(&cache_key).await {
-        return Ok(Json(cached));
+    let mut projects = match state.projects_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => load_projects(&state, is_admin, cache_key).await?,
+    };
+
+    // Admins edit the default-locale text, so they get it untranslated.
+    if is_admin {
+        return Ok(Json(projects).into_response());
     }
 
+    let locale = i18n::request_locale(lang.as_deref(), &headers, &state.site);
+    let translations = translations::load(&state, TranslatableKind::Projects).await?;
+    for project in &mut projects {
+        i18n::localize(project, &translations, &locale, &state.site);
+    }
+    let summaries: Vec<ProjectSummary> = projects.into_iter().map(ProjectSummary::from).collect();
+    Ok(i18n::localized_json(summaries, &locale))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{slug}",
+    params(
+        ("slug" = String, Path, description = "Project slug"),
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
+    responses(
+        (status = 200, description = "Project with its case study", body = Project),
+        (status = 404, description = "No active project with this slug")
+    )
+)]
+pub async fn get_project(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cache_key = String::from("projects");
+    // Served from the cached list of active projects, so edits invalidate it too.
+    let projects = match state.projects_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => load_projects(&state, false, cache_key).await?,
+    };
+    let mut project = projects.into_iter().find(|p| p.slug == slug).ok_or(AppError::NotFound)?;
+
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
+    let translations = translations::load(&state, TranslatableKind::Projects).await?;
+    i18n::localize(&mut project, &translations, &locale, &state.site);
+    project.case_study_html = project.case_study.as_deref().map(import::render_markdown);
+
+    Ok(i18n::localized_json(project, &locale))
+}
+
+async fn invalidate_projects(state: &AppState) {
+    state.projects_cache.invalidate(&String::from("projects")).await;
+    state.projects_cache.invalidate(&String::from("projects_admin")).await;
+    // The sitemap, cached with the feeds, lists project pages.
+    state.feeds_cache.invalidate_all().await;
+}
+
+async fn load_projects(state: &AppState, is_admin: bool, cache_key: String) -> Result<Vec<Project>, AppError> {
     let query_str = if is_admin {
-        "SELECT id, title, description, image, skills, link, is_active, created_at, updated_at FROM projects ORDER BY id DESC"
+        format!("SELECT {} FROM projects ORDER BY sort_order, id DESC", PROJECT_COLUMNS)
     } else {
-        "SELECT id, title, description, image, skills, link, is_active, created_at, updated_at FROM projects WHERE is_active = true ORDER BY id DESC"
+        format!("SELECT {} FROM projects WHERE is_active = true ORDER BY sort_order, id DESC", PROJECT_COLUMNS)
     };
 
-    let projects = sqlx::query_as::<_, Project>(query_str)
+    let projects = sqlx::query_as::<_, Project>(&query_str)
         .fetch_all(&state.pool)
         .await?;
 
     state.projects_cache.insert(cache_key, projects.clone()).await;
-    Ok(Json(projects))
+    Ok(projects)
 }
 
 #[derive(Deserialize)]
@@ -55,14 +134,86 @@ pub struct ProjectPayload {
     pub skills: Option<Vec<String>>,
     pub link: Option<String>,
     pub is_active: Option<bool>,
+    /// Defaults to the slugified title
+    pub slug: Option<String>,
+    pub role: Option<String>,
+    pub team_size: Option<i32>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Markdown
+    pub case_study: Option<String>,
+}
+
+/// Slugs taken by static routes next to `/api/projects/:slug`.
+const RESERVED_SLUGS: &[&str] = &["reorder"];
+
+/// The slug the payload asks for, if it sets one.
+fn requested_slug(payload: &ProjectPayload) -> Result<Option<String>, AppError> {
+    let Some(raw) = payload.slug.as_deref().filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let slug = slugify(raw);
+    if slug.is_empty() {
+        return Err(AppError::ValidationError("slug must contain letters or digits".to_string()));
+    }
+    if RESERVED_SLUGS.contains(&slug.as_str()) {
+        return Err(AppError::ValidationError(format!("'{}' is reserved; choose another slug", slug)));
+    }
+    Ok(Some(slug))
+}
+
+fn validate_project(payload: &ProjectPayload) -> Result<(), AppError> {
+    if payload.team_size.is_some_and(|size| size < 1) {
+        return Err(AppError::ValidationError("team_size must be at least 1".to_string()));
+    }
+    if let (Some(start), Some(end)) = (payload.start_date, payload.end_date) {
+        if end < start {
+            return Err(AppError::ValidationError("end_date must not be before start_date".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// `base`, suffixed like post slugs when another project or a static route has it.
+async fn unique_slug(state: &AppState, base: &str) -> Result<String, AppError> {
+    let mut taken: HashSet<String> = sqlx::query_scalar::<_, String>("SELECT slug FROM projects WHERE slug IS NOT NULL")
+        .fetch_all(&state.pool)
+        .await?
+        .into_iter()
+        .collect();
+    taken.extend(RESERVED_SLUGS.iter().map(|s| s.to_string()));
+    Ok(next_free_slug(base, &taken))
+}
+
+/// Fail if another project (not `id`) already uses `slug`.
+async fn ensure_slug_free(state: &AppState, slug: &str, id: Option<i32>) -> Result<(), AppError> {
+    let taken = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM projects WHERE slug = $1 AND id IS DISTINCT FROM $2)")
+        .bind(slug)
+        .bind(id)
+        .fetch_one(&state.pool)
+        .await?;
+    if taken {
+        return Err(AppError::ValidationError(format!("A project with slug '{}' already exists", slug)));
+    }
+    Ok(())
 }
 
 pub async fn create_project(
     State(state): State<AppState>,
     Json(payload): Json<ProjectPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    validate_project(&payload)?;
+    let base = match requested_slug(&payload)? {
+        Some(slug) => slug,
+        None => slugify(&payload.title),
+    };
+    if base.is_empty() {
+        return Err(AppError::ValidationError("Could not derive a slug from the title".to_string()));
+    }
+    let slug = unique_slug(&state, &base).await?;
+
     sqlx::query(
-        "INSERT INTO projects (title, description, image, skills, link, is_active) VALUES ($1, $2, $3, $4, $5, $6)"
+        "INSERT INTO projects (title, description, image, skills, link, is_active, slug, role, team_size, start_date, end_date, case_study, sort_order) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, (SELECT COALESCE(MIN(sort_order), 0) - 1 FROM projects))"
     )
     .bind(&payload.title)
     .bind(&payload.description)
@@ -70,11 +221,16 @@ pub async fn create_project(
     .bind(&payload.skills)
     .bind(payload.link.unwrap_or_default())
     .bind(payload.is_active.unwrap_or(true))
+    .bind(&slug)
+    .bind(&payload.role)
+    .bind(payload.team_size)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(&payload.case_study)
     .execute(&state.pool)
     .await?;
 
-    state.projects_cache.invalidate(&String::from("projects")).await;
-    state.projects_cache.invalidate(&String::from("projects_admin")).await;
+    invalidate_projects(&state).await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -84,8 +240,15 @@ pub async fn update_project(
     State(state): State<AppState>,
     Json(payload): Json<ProjectPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
+    validate_project(&payload)?;
+    // Without an explicit slug the stored one stays, so renaming a project doesn't break its URL.
+    let slug = requested_slug(&payload)?;
+    if let Some(slug) = &slug {
+        ensure_slug_free(&state, slug, Some(id)).await?;
+    }
+
     let result = sqlx::query(
-        "UPDATE projects SET title = $1, description = $2, image = $3, skills = $4, link = $5, is_active = $6, updated_at = NOW() WHERE id = $7"
+        "UPDATE projects SET title = $1, description = $2, image = $3, skills = $4, link = $5, is_active = $6, slug = COALESCE($7, slug), role = $8, team_size = $9, start_date = $10, end_date = $11, case_study = $12, updated_at = NOW() WHERE id = $13"
     )
     .bind(&payload.title)
     .bind(&payload.description)
@@ -93,6 +256,12 @@ pub async fn update_project(
     .bind(&payload.skills)
     .bind(payload.link.unwrap_or_default())
     .bind(payload.is_active.unwrap_or(true))
+    .bind(&slug)
+    .bind(&payload.role)
+    .bind(payload.team_size)
+    .bind(payload.start_date)
+    .bind(payload.end_date)
+    .bind(&payload.case_study)
     .bind(id)
     .execute(&state.pool)
     .await?;
@@ -101,8 +270,7 @@ pub async fn update_project(
         return Err(AppError::NotFound);
     }
 
-    state.projects_cache.invalidate(&String::from("projects")).await;
-    state.projects_cache.invalidate(&String::from("projects_admin")).await;
+    invalidate_projects(&state).await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
@@ -120,8 +288,66 @@ pub async fn delete_project(
         return Err(AppError::NotFound);
     }
 
-    state.projects_cache.invalidate(&String::from("projects")).await;
-    state.projects_cache.invalidate(&String::from("projects_admin")).await;
+    invalidate_projects(&state).await;
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
+
+// PUT /api/projects/reorder - Set the display order from a complete, ordered id list
+pub async fn reorder_projects(
+    State(state): State<AppState>,
+    Json(payload): Json<ReorderPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    ordering::apply_order(&state.pool, "projects", &payload.ids).await?;
+
+    invalidate_projects(&state).await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(title: &str, slug: Option<&str>) -> ProjectPayload {
+        ProjectPayload {
+            title: title.to_string(),
+            description: String::new(),
+            image: None,
+            skills: None,
+            link: None,
+            is_active: None,
+            slug: slug.map(str::to_string),
+            role: None,
+            team_size: None,
+            start_date: None,
+            end_date: None,
+            case_study: None,
+        }
+    }
+
+    #[test]
+    fn test_requested_slug() {
+        assert_eq!(requested_slug(&payload("My Portfolio Site", None)).unwrap(), None);
+        assert_eq!(requested_slug(&payload("Site", Some(""))).unwrap(), None);
+        assert_eq!(requested_slug(&payload("Whatever", Some("Case Study 1"))).unwrap().as_deref(), Some("case-study-1"));
+        assert!(requested_slug(&payload("Site", Some("!!!"))).is_err());
+        assert!(requested_slug(&payload("Site", Some("Reorder"))).is_err());
+    }
+
+    #[test]
+    fn test_validate_project() {
+        assert!(validate_project(&payload("Site", None)).is_ok());
+
+        let mut p = payload("Site", None);
+        p.team_size = Some(0);
+        assert!(validate_project(&p).is_err());
+
+        let mut p = payload("Site", None);
+        p.start_date = NaiveDate::from_ymd_opt(2024, 5, 1);
+        p.end_date = NaiveDate::from_ymd_opt(2024, 4, 1);
+        assert!(validate_project(&p).is_err());
+        p.end_date = NaiveDate::from_ymd_opt(2024, 5, 1);
+        assert!(validate_project(&p).is_ok());
+    }
+}
@@ -0,0 +1,277 @@
+use axum::{extract::{State, Path, Query}, Json};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use crate::models::BlogPostRevision;
+use crate::error::AppError;
+use crate::highlight;
+use crate::state::AppState;
+use super::authors;
+
+/// Copy the live post (with its category, tag and author ids) into `blog_post_revisions`.
+/// Returns false if the post doesn't exist.
+pub async fn snapshot_post<'e, E>(executor: E, post_id: i32) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let result = sqlx::query(
+        r#"
+INSERT INTO blog_post_revisions (post_id, title, excerpt, content, content_markdown, author, meta_title, meta_description, meta_keywords, category_ids, tag_ids, author_ids)
+SELECT p.id, p.title, p.excerpt, p.content, p.content_markdown, p.author, p.meta_title, p.meta_description, p.meta_keywords,
+    ARRAY(SELECT category_id FROM blog_post_categories WHERE post_id = p.id ORDER BY category_id),
+    ARRAY(SELECT tag_id FROM blog_post_tags WHERE post_id = p.id ORDER BY tag_id),
+    ARRAY(SELECT author_id FROM blog_post_authors WHERE post_id = p.id ORDER BY position, author_id)
+FROM blog_posts p
+WHERE p.id = $1
+"#
+    )
+    .bind(post_id)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The live post shaped as a revision (id 0) so it can be diffed against stored ones.
+async fn current_as_revision(state: &AppState, post_id: i32) -> Result<Option<BlogPostRevision>, sqlx::Error> {
+    sqlx::query_as::<_, BlogPostRevision>(
+        r#"
+SELECT 0 AS id, p.id AS post_id, p.title, p.excerpt, p.content, p.content_markdown, p.author, p.meta_title, p.meta_description, p.meta_keywords,
+    ARRAY(SELECT category_id FROM blog_post_categories WHERE post_id = p.id ORDER BY category_id) AS category_ids,
+    ARRAY(SELECT tag_id FROM blog_post_tags WHERE post_id = p.id ORDER BY tag_id) AS tag_ids,
+    ARRAY(SELECT author_id FROM blog_post_authors WHERE post_id = p.id ORDER BY position, author_id) AS author_ids,
+    p.updated_at AS created_at
+FROM blog_posts p
+WHERE p.id = $1
+"#
+    )
+    .bind(post_id)
+    .fetch_optional(&state.pool)
+    .await
+}
+
+async fn fetch_revision(state: &AppState, post_id: i32, rev: i32) -> Result<BlogPostRevision, AppError> {
+    sqlx::query_as::<_, BlogPostRevision>(
+        "SELECT id, post_id, title, excerpt, content, content_markdown, author, meta_title, meta_description, meta_keywords, category_ids, tag_ids, author_ids, created_at FROM blog_post_revisions WHERE id = $1 AND post_id = $2"
+    )
+    .bind(rev)
+    .bind(post_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(AppError::NotFound)
+}
+
+#[derive(Serialize, Debug)]
+pub struct FieldDiff {
+    pub field: String,
+    /// Unified diff of the field's text, one line per array element for lists
+    pub diff: String,
+}
+
+#[derive(Serialize)]
+pub struct RevisionDiff {
+    pub post_id: i32,
+    pub from: i32,
+    /// None when comparing against the live post
+    pub to: Option<i32>,
+    pub changes: Vec<FieldDiff>,
+}
+
+fn lines<T: ToString>(items: &[T]) -> String {
+    items.iter().map(|i| format!("{}\n", i.to_string())).collect()
+}
+
+fn diffable_fields(rev: &BlogPostRevision) -> Vec<(&'static str, String)> {
+    vec![
+        ("title", rev.title.clone()),
+        ("excerpt", rev.excerpt.clone()),
+        ("content", rev.content.clone()),
+        ("content_markdown", rev.content_markdown.clone().unwrap_or_default()),
+        ("author", rev.author.clone().unwrap_or_default()),
+        ("meta_title", rev.meta_title.clone().unwrap_or_default()),
+        ("meta_description", rev.meta_description.clone().unwrap_or_default()),
+        ("meta_keywords", lines(rev.meta_keywords.as_deref().unwrap_or_default())),
+        ("categories", lines(&rev.category_ids)),
+        ("tags", lines(&rev.tag_ids)),
+    ]
+}
+
+/// Unified diff of one field's text.
+pub(crate) fn diff_field(field: &str, old: &str, new: &str) -> FieldDiff {
+    FieldDiff {
+        field: field.to_string(),
+        diff: TextDiff::from_lines(old, new)
+            .unified_diff()
+            .context_radius(3)
+            .header("from", "to")
+            .to_string(),
+    }
+}
+
+/// Per-field unified diffs between two revisions; unchanged fields are omitted.
+pub fn diff_revisions(from: &BlogPostRevision, to: &BlogPostRevision) -> Vec<FieldDiff> {
+    diffable_fields(from)
+        .into_iter()
+        .zip(diffable_fields(to))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| diff_field(field, &old, &new))
+        .collect()
+}
+
+// GET /api/blog/admin/posts/:id/revisions - Newest first
+pub async fn get_revisions(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<BlogPostRevision>>, AppError> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM blog_posts WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&state.pool)
+        .await?;
+
+    if !exists {
+        return Err(AppError::NotFound);
+    }
+
+    let revisions = sqlx::query_as::<_, BlogPostRevision>(
+        "SELECT id, post_id, title, excerpt, content, content_markdown, author, meta_title, meta_description, meta_keywords, category_ids, tag_ids, author_ids, created_at FROM blog_post_revisions WHERE post_id = $1 ORDER BY id DESC"
+    )
+    .bind(id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(revisions))
+}
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: Option<i32>,
+}
+
+// GET /api/blog/admin/posts/:id/revisions/diff?from=<rev>&to=<rev> - Omit `to` to diff against the live post
+pub async fn get_revision_diff(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<RevisionDiff>, AppError> {
+    let from = fetch_revision(&state, id, query.from).await?;
+    let to = match query.to {
+        Some(rev) => fetch_revision(&state, id, rev).await?,
+        None => current_as_revision(&state, id).await?.ok_or(AppError::NotFound)?,
+    };
+
+    Ok(Json(RevisionDiff {
+        post_id: id,
+        from: from.id,
+        to: query.to,
+        changes: diff_revisions(&from, &to),
+    }))
+}
+
+// POST /api/blog/admin/posts/:id/revisions/:rev/restore - The replaced state is kept as a new revision
+pub async fn restore_revision(
+    Path((id, rev)): Path<(i32, i32)>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let revision = fetch_revision(&state, id, rev).await?;
+
+    let mut tx = state.pool.begin().await?;
+
+    if !snapshot_post(&mut *tx, id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    sqlx::query(
+        "UPDATE blog_posts SET title = $1, excerpt = $2, content = $3, content_markdown = $4, author = $5, meta_title = $6, meta_description = $7, meta_keywords = $8, updated_at = NOW() WHERE id = $9"
+    )
+    .bind(&revision.title)
+    .bind(&revision.excerpt)
+    // Revisions saved before highlighting moved to save time hold plain code blocks.
+    .bind(highlight::highlight_html(&revision.content))
+    .bind(&revision.content_markdown)
+    .bind(&revision.author)
+    .bind(&revision.meta_title)
+    .bind(&revision.meta_description)
+    .bind(&revision.meta_keywords)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    // Categories or tags deleted since the revision was taken are skipped.
+    sqlx::query("DELETE FROM blog_post_categories WHERE post_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("INSERT INTO blog_post_categories (post_id, category_id) SELECT $1, id FROM blog_categories WHERE id = ANY($2)")
+        .bind(id)
+        .bind(&revision.category_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM blog_post_tags WHERE post_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("INSERT INTO blog_post_tags (post_id, tag_id) SELECT $1, id FROM blog_tags WHERE id = ANY($2)")
+        .bind(id)
+        .bind(&revision.tag_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    // Revisions from before authors existed only have the byline.
+    let author_ids = if revision.author_ids.is_empty() {
+        authors::resolve_authors(&mut tx, revision.author.as_slice()).await?
+    } else {
+        revision.author_ids.clone()
+    };
+    authors::sync_authors(&mut tx, id, &author_ids).await?;
+
+    tx.commit().await?;
+
+    state.invalidate_posts().await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(title: &str, content: &str, tag_ids: Vec<i32>) -> BlogPostRevision {
+        BlogPostRevision {
+            id: 1,
+            post_id: 1,
+            title: title.to_string(),
+            excerpt: "Excerpt".to_string(),
+            content: content.to_string(),
+            content_markdown: None,
+            author: None,
+            meta_title: None,
+            meta_description: None,
+            meta_keywords: None,
+            category_ids: vec![1],
+            tag_ids,
+            author_ids: Vec::new(),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_revisions_identical() {
+        let a = revision("Title", "Line one\nLine two\n", vec![1, 2]);
+        assert!(diff_revisions(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_revisions_reports_changed_fields_only() {
+        let a = revision("Title", "Line one\nLine two\n", vec![1, 2]);
+        let b = revision("Title", "Line one\nLine 2\n", vec![2, 3]);
+
+        let changes = diff_revisions(&a, &b);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["content", "tags"]);
+        assert!(changes[0].diff.contains("-Line two"));
+        assert!(changes[0].diff.contains("+Line 2"));
+        assert!(changes[1].diff.contains("-1"));
+        assert!(changes[1].diff.contains("+3"));
+    }
+}
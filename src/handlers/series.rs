@@ -0,0 +1,280 @@
+use axum::{
+    extract::{State, Path},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::models::{BlogSeries, SeriesPost};
+use crate::error::AppError;
+use crate::state::AppState;
+use super::blog::slugify;
+
+/// `series` column for a post row aliased `post`: the series it belongs to, its part
+/// number among the published parts, and the previous/next published parts.
+/// The post itself always counts, so a previewed draft still sees its neighbours.
+pub(crate) const SERIES_COLUMN: &str = r#"
+    (
+        SELECT json_build_object(
+            'id', s.id,
+            'title', s.title,
+            'slug', s.slug,
+            'position', m.position,
+            'total', m.total,
+            'previous', m.previous,
+            'next', m.next
+        )
+        FROM (
+            SELECT sp.series_id, sp.post_id,
+                ROW_NUMBER() OVER w AS position,
+                COUNT(*) OVER (PARTITION BY sp.series_id) AS total,
+                LAG(json_build_object('title', mp.title, 'slug', mp.slug)) OVER w AS previous,
+                LEAD(json_build_object('title', mp.title, 'slug', mp.slug)) OVER w AS next
+            FROM blog_series_posts sp
+            JOIN blog_posts mp ON mp.id = sp.post_id
+            WHERE sp.series_id = (SELECT series_id FROM blog_series_posts WHERE post_id = post.id)
+              AND ((mp.published = true AND mp.scheduled = false) OR mp.id = post.id)
+            WINDOW w AS (ORDER BY sp.position, sp.post_id)
+        ) m
+        JOIN blog_series s ON s.id = m.series_id
+        WHERE m.post_id = post.id
+    ) as series
+"#;
+
+const SERIES_SELECT: &str = r#"
+SELECT s.id, s.title, s.slug, s.description, s.created_at, s.updated_at,
+    (
+        SELECT COUNT(*) FROM blog_series_posts sp
+        JOIN blog_posts p ON p.id = sp.post_id
+        WHERE sp.series_id = s.id AND p.published = true AND p.scheduled = false
+    ) as post_count
+FROM blog_series s
+"#;
+
+#[derive(Serialize, ToSchema)]
+pub struct SeriesDetail {
+    #[serde(flatten)]
+    pub series: BlogSeries,
+    pub posts: Vec<SeriesPost>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/series",
+    responses(
+        (status = 200, description = "List post series", body = [BlogSeries])
+    )
+)]
+pub async fn get_series_list(State(state): State<AppState>) -> Result<Json<Vec<BlogSeries>>, AppError> {
+    let series = sqlx::query_as::<_, BlogSeries>(&format!("{} ORDER BY s.title", SERIES_SELECT))
+        .fetch_all(&state.pool)
+        .await?;
+
+    Ok(Json(series))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/blog/series/{slug}",
+    params(
+        ("slug" = String, Path, description = "Series slug")
+    ),
+    responses(
+        (status = 200, description = "A series with its published parts in order", body = SeriesDetail),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_series(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<SeriesDetail>, AppError> {
+    let series = sqlx::query_as::<_, BlogSeries>(&format!("{} WHERE s.slug = $1", SERIES_SELECT))
+        .bind(&slug)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let posts = sqlx::query_as::<_, SeriesPost>(
+        r#"
+SELECT p.id, p.title, p.slug, p.excerpt, p.featured_image, p.published_at,
+    ROW_NUMBER() OVER (ORDER BY sp.position, sp.post_id) AS position
+FROM blog_series_posts sp
+JOIN blog_posts p ON p.id = sp.post_id
+WHERE sp.series_id = $1 AND p.published = true AND p.scheduled = false
+ORDER BY sp.position, sp.post_id
+"#
+    )
+    .bind(series.id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(SeriesDetail { series, posts }))
+}
+
+// ========== Admin Series CRUD ==========
+
+#[derive(Deserialize)]
+pub struct SeriesPayload {
+    pub title: String,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+}
+
+fn series_slug(payload: &SeriesPayload) -> String {
+    match &payload.slug {
+        Some(s) if !s.is_empty() => s.clone(),
+        _ => slugify(&payload.title),
+    }
+}
+
+// POST /api/blog/admin/series
+pub async fn create_series(
+    State(state): State<AppState>,
+    Json(payload): Json<SeriesPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let id = sqlx::query_scalar::<_, i32>(
+        "INSERT INTO blog_series (title, slug, description) VALUES ($1, $2, $3) RETURNING id"
+    )
+    .bind(&payload.title)
+    .bind(series_slug(&payload))
+    .bind(&payload.description)
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok(Json(serde_json::json!({ "success": true, "id": id })))
+}
+
+// PUT /api/blog/admin/series/:id
+pub async fn update_series(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<SeriesPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("UPDATE blog_series SET title = $1, slug = $2, description = $3, updated_at = NOW() WHERE id = $4")
+        .bind(&payload.title)
+        .bind(series_slug(&payload))
+        .bind(&payload.description)
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// DELETE /api/blog/admin/series/:id - Posts stay, only the grouping goes
+pub async fn delete_series(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let result = sqlx::query("DELETE FROM blog_series WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SeriesPostsPayload {
+    /// Every part of the series, in reading order
+    pub post_ids: Vec<i32>,
+}
+
+/// Ids listed more than once, in first-seen order.
+fn duplicate_ids(ids: &[i32]) -> Vec<i32> {
+    let mut seen = std::collections::HashSet::new();
+    let mut duplicates = Vec::new();
+    for id in ids {
+        if !seen.insert(*id) && !duplicates.contains(id) {
+            duplicates.push(*id);
+        }
+    }
+    duplicates
+}
+
+// PUT /api/blog/admin/series/:id/posts - Set membership and order in one go.
+// Posts listed here leave any other series they were in.
+pub async fn set_series_posts(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<SeriesPostsPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let duplicates = duplicate_ids(&payload.post_ids);
+    if !duplicates.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "Duplicate post ids: {}",
+            duplicates.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    sqlx::query_scalar::<_, i32>("SELECT id FROM blog_series WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let unknown = sqlx::query_scalar::<_, i32>(
+        "SELECT u.id FROM UNNEST($1::int[]) AS u(id) WHERE NOT EXISTS (SELECT 1 FROM blog_posts p WHERE p.id = u.id) ORDER BY u.id"
+    )
+    .bind(&payload.post_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+    if !unknown.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "Unknown post ids: {}",
+            unknown.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    sqlx::query("DELETE FROM blog_series_posts WHERE series_id = $1 OR post_id = ANY($2)")
+        .bind(id)
+        .bind(&payload.post_ids)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO blog_series_posts (series_id, post_id, position) SELECT $1, u.post_id, u.position FROM UNNEST($2::int[]) WITH ORDINALITY AS u(post_id, position)"
+    )
+    .bind(id)
+    .bind(&payload.post_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("UPDATE blog_series SET updated_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_ids() {
+        assert!(duplicate_ids(&[1, 2, 3]).is_empty());
+        assert_eq!(duplicate_ids(&[3, 1, 3, 2, 1, 3]), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_series_slug_falls_back_to_title() {
+        let payload = SeriesPayload { title: "Rust From Zero".to_string(), slug: None, description: None };
+        assert_eq!(series_slug(&payload), "rust-from-zero");
+
+        let payload = SeriesPayload { title: "Rust".to_string(), slug: Some("rfz".to_string()), description: None };
+        assert_eq!(series_slug(&payload), "rfz");
+    }
+}
@@ -0,0 +1,194 @@
+use axum::{
+    extract::{State, Query},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use crate::error::AppError;
+use crate::sitemap::{self, SitemapEntry};
+use crate::state::AppState;
+use super::feeds::{cached_feed, conditional_response};
+
+const SITEMAP_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// Tables whose newest `updated_at` dates a static profile section.
+fn section_lastmod_sql(section: &str) -> Option<&'static str> {
+    match section {
+        "about" => Some("SELECT MAX(updated_at) FROM about"),
+        "skills" => Some("SELECT MAX(updated_at) FROM skills"),
+        "experience" => Some("SELECT MAX(updated_at) FROM experience_timeline"),
+        "projects" => Some("SELECT MAX(updated_at) FROM projects WHERE is_active = true"),
+        "contact" => Some("SELECT GREATEST((SELECT MAX(updated_at) FROM contact_info), (SELECT MAX(updated_at) FROM social_links))"),
+        _ => None,
+    }
+}
+
+const POST_LASTMOD: &str = "COALESCE(p.updated_at, p.published_at, p.created_at)";
+
+/// Every public frontend page: home, profile sections, active projects (with the
+/// projects section), the blog index, posts and category/tag archives that have
+/// at least one published post.
+async fn collect_entries(state: &AppState) -> Result<Vec<SitemapEntry>, AppError> {
+    let site = &state.site;
+    let rules = &site.sitemap;
+
+    let posts = sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(&format!(
+        "SELECT p.slug, {} FROM blog_posts p WHERE p.published = true AND p.scheduled = false ORDER BY p.published_at DESC, p.id DESC",
+        POST_LASTMOD
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    let categories = sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(&format!(
+        r#"SELECT c.slug, MAX({})
+FROM blog_categories c
+JOIN blog_post_categories pc ON pc.category_id = c.id
+JOIN blog_posts p ON p.id = pc.post_id AND p.published = true AND p.scheduled = false
+GROUP BY c.slug ORDER BY c.slug"#,
+        POST_LASTMOD
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    let tags = sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(&format!(
+        r#"SELECT t.slug, MAX({})
+FROM blog_tags t
+JOIN blog_post_tags pt ON pt.tag_id = t.id
+JOIN blog_posts p ON p.id = pt.post_id AND p.published = true AND p.scheduled = false
+GROUP BY t.slug ORDER BY t.slug"#,
+        POST_LASTMOD
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    let projects = if rules.sections.iter().any(|s| s == "projects") {
+        sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(
+            "SELECT slug, COALESCE(updated_at, created_at) FROM projects WHERE is_active = true ORDER BY sort_order, id DESC"
+        )
+        .fetch_all(&state.pool)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    let mut sections = Vec::with_capacity(rules.sections.len());
+    for section in &rules.sections {
+        let lastmod = match section_lastmod_sql(section) {
+            Some(sql) => sqlx::query_scalar::<_, Option<NaiveDateTime>>(sql)
+                .fetch_one(&state.pool)
+                .await?,
+            None => None,
+        };
+        sections.push(SitemapEntry {
+            loc: site.page_url(section),
+            lastmod,
+            priority: rules.section_priority,
+        });
+    }
+
+    let latest_post = posts.iter().filter_map(|(_, lastmod)| *lastmod).max();
+    let latest_any = sections.iter().filter_map(|e| e.lastmod).chain(latest_post).max();
+
+    let mut entries = vec![SitemapEntry {
+        loc: site.page_url(""),
+        lastmod: latest_any,
+        priority: rules.home_priority,
+    }];
+    entries.extend(sections);
+    entries.extend(projects.into_iter().map(|(slug, lastmod)| SitemapEntry {
+        loc: site.project_url(&slug),
+        lastmod,
+        priority: rules.post_priority,
+    }));
+    entries.push(SitemapEntry {
+        loc: site.page_url(&site.blog_path),
+        lastmod: latest_post,
+        priority: rules.section_priority,
+    });
+    entries.extend(posts.into_iter().map(|(slug, lastmod)| SitemapEntry {
+        loc: site.post_url(&slug),
+        lastmod,
+        priority: rules.post_priority,
+    }));
+    entries.extend(categories.into_iter().map(|(slug, lastmod)| SitemapEntry {
+        loc: site.category_url(&slug),
+        lastmod,
+        priority: rules.archive_priority,
+    }));
+    entries.extend(tags.into_iter().map(|(slug, lastmod)| SitemapEntry {
+        loc: site.tag_url(&slug),
+        lastmod,
+        priority: rules.archive_priority,
+    }));
+
+    Ok(entries)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SitemapQuery {
+    pub page: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/sitemap.xml",
+    params(
+        ("page" = Option<usize>, Query, description = "Sitemap file number when the index is in use")
+    ),
+    responses(
+        (status = 200, description = "Sitemap, or a sitemap index when there are more URLs than fit in one file", body = String, content_type = "application/xml"),
+        (status = 304, description = "Not modified"),
+        (status = 404, description = "Page out of range")
+    )
+)]
+pub async fn sitemap(
+    State(state): State<AppState>,
+    Query(query): Query<SitemapQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let cache_key = match query.page {
+        Some(page) => format!("sitemap:{}", page),
+        None => String::from("sitemap"),
+    };
+
+    if let Some(cached) = state.feeds_cache.get(&cache_key).await {
+        return Ok(conditional_response(&headers, SITEMAP_CONTENT_TYPE, cached));
+    }
+
+    let entries = collect_entries(&state).await?;
+    let max_urls = state.site.sitemap.max_urls;
+
+    let (body, chunk) = match query.page {
+        None if entries.len() <= max_urls => (sitemap::render_urlset(&entries), &entries[..]),
+        None => {
+            let index = sitemap::render_index(&state.site.api_path_url("/sitemap.xml"), &entries, max_urls);
+            (index, &entries[..])
+        }
+        Some(page) => {
+            let chunk = entries
+                .chunks(max_urls)
+                .nth(page.checked_sub(1).ok_or(AppError::NotFound)?)
+                .ok_or(AppError::NotFound)?;
+            (sitemap::render_urlset(chunk), chunk)
+        }
+    };
+
+    let file = cached_feed(body, chunk.iter().filter_map(|e| e.lastmod).max());
+    state.feeds_cache.insert(cache_key, file.clone()).await;
+    Ok(conditional_response(&headers, SITEMAP_CONTENT_TYPE, file))
+}
+
+#[utoipa::path(
+    get,
+    path = "/robots.txt",
+    responses(
+        (status = 200, description = "Crawler rules pointing at the sitemap", body = String, content_type = "text/plain")
+    )
+)]
+pub async fn robots(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        sitemap::render_robots(&state.site),
+    )
+}
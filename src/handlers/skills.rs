@@ -1,31 +1,54 @@
-use axum::{extract::{State, Path}, Json};
+use axum::{
+    extract::{State, Path, Query},
+    http::HeaderMap,
+    response::Response,
+    Json,
+};
 use serde::Deserialize;
+use crate::i18n::{self, TranslatableKind};
 use crate::models::Skill;
 use crate::error::AppError;
+use crate::ordering::{self, ReorderPayload};
 use crate::state::AppState;
+use super::translations::{self, LangQuery};
 
 #[utoipa::path(
     get,
     path = "/api/skills",
+    params(
+        ("lang" = Option<String>, Query, description = "Locale; defaults to Accept-Language, then the site language")
+    ),
     responses(
         (status = 200, description = "Get list of skills", body = [Skill])
     )
 )]
-pub async fn get_skills(State(state): State<AppState>) -> Result<Json<Vec<Skill>>, AppError> {
+pub async fn get_skills(
+    State(state): State<AppState>,
+    Query(query): Query<LangQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
     let cache_key = String::from("skills");
-    
-    if let Some(cached) = state.skills_cache.get(&cache_key).await {
-        return Ok(Json(cached));
-    }
+    let locale = i18n::request_locale(query.lang.as_deref(), &headers, &state.site);
 
-    let skills = sqlx::query_as::<_, Skill>(
-        "SELECT id, icon, title, description, created_at, updated_at FROM skills ORDER BY id ASC"
-    )
-    .fetch_all(&state.pool)
-    .await?;
+    let mut skills = match state.skills_cache.get(&cache_key).await {
+        Some(cached) => cached,
+        None => {
+            let skills = sqlx::query_as::<_, Skill>(
+                "SELECT id, icon, title, description, created_at, updated_at FROM skills ORDER BY sort_order, id ASC"
+            )
+            .fetch_all(&state.pool)
+            .await?;
 
-    state.skills_cache.insert(cache_key, skills.clone()).await;
-    Ok(Json(skills))
+            state.skills_cache.insert(cache_key, skills.clone()).await;
+            skills
+        }
+    };
+
+    let translations = translations::load(&state, TranslatableKind::Skills).await?;
+    for skill in &mut skills {
+        i18n::localize(skill, &translations, &locale, &state.site);
+    }
+    Ok(i18n::localized_json(skills, &locale))
 }
 
 #[derive(Deserialize)]
@@ -40,7 +63,7 @@ pub async fn create_skill(
     Json(payload): Json<SkillPayload>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     sqlx::query(
-        "INSERT INTO skills (icon, title, description) VALUES ($1, $2, $3)"
+        "INSERT INTO skills (icon, title, description, sort_order) VALUES ($1, $2, $3, (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM skills))"
     )
     .bind(&payload.icon)
     .bind(&payload.title)
@@ -94,3 +117,15 @@ pub async fn delete_skill(
 
     Ok(Json(serde_json::json!({ "success": true })))
 }
+
+// PUT /api/skills/reorder - Set the display order from a complete, ordered id list
+pub async fn reorder_skills(
+    State(state): State<AppState>,
+    Json(payload): Json<ReorderPayload>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    ordering::apply_order(&state.pool, "skills", &payload.ids).await?;
+
+    state.skills_cache.invalidate(&String::from("skills")).await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
@@ -0,0 +1,224 @@
+use axum::{
+    extract::{State, Path},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use crate::models::BlogTag;
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// Lock a tag for the rest of the transaction, returning its slug.
+async fn lock_tag(conn: &mut PgConnection, id: i32) -> Result<String, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT slug FROM blog_tags WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::NotFound)
+}
+
+/// Point `slug` at `tag_id` for redirects, and make sure the tag's current slug
+/// isn't also listed as a retired one.
+pub(crate) async fn record_old_slug(conn: &mut PgConnection, slug: &str, tag_id: i32) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO blog_tag_slug_history (slug, tag_id) VALUES ($1, $2) ON CONFLICT (slug) DO UPDATE SET tag_id = EXCLUDED.tag_id, created_at = NOW()"
+    )
+    .bind(slug)
+    .bind(tag_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM blog_tag_slug_history WHERE slug = (SELECT slug FROM blog_tags WHERE id = $1)")
+        .bind(tag_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Current slug of a tag that used to be called `slug`.
+pub(crate) async fn current_slug(state: &AppState, slug: &str) -> Result<Option<String>, AppError> {
+    let current = sqlx::query_scalar::<_, String>(
+        "SELECT t.slug FROM blog_tag_slug_history h JOIN blog_tags t ON t.id = h.tag_id WHERE h.slug = $1"
+    )
+    .bind(slug)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    Ok(current)
+}
+
+#[derive(Deserialize)]
+pub struct MergeTagPayload {
+    /// Tag that takes over the posts
+    pub into: i32,
+}
+
+#[derive(Serialize)]
+pub struct MergeTagResponse {
+    pub success: bool,
+    pub tag: BlogTag,
+    /// Posts that gained the target tag (posts that already had it aren't counted)
+    pub moved_posts: u64,
+}
+
+// POST /api/blog/admin/tags/:id/merge - Move every post from this tag onto `into`,
+// then delete it. Its slug (and any older ones) redirect to the target.
+pub async fn merge_tag(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<MergeTagPayload>,
+) -> Result<Json<MergeTagResponse>, AppError> {
+    if payload.into == id {
+        return Err(AppError::ValidationError("Cannot merge a tag into itself".to_string()));
+    }
+
+    let mut tx = state.pool.begin().await?;
+
+    // Lock in id order so two opposite merges can't deadlock.
+    let (first, second) = if id < payload.into { (id, payload.into) } else { (payload.into, id) };
+    let first_slug = lock_tag(&mut tx, first).await?;
+    let second_slug = lock_tag(&mut tx, second).await?;
+    let source_slug = if first == id { first_slug } else { second_slug };
+
+    let moved = sqlx::query(
+        r#"
+INSERT INTO blog_post_tags (post_id, tag_id)
+SELECT s.post_id, $2 FROM blog_post_tags s
+WHERE s.tag_id = $1
+  AND NOT EXISTS (SELECT 1 FROM blog_post_tags d WHERE d.post_id = s.post_id AND d.tag_id = $2)
+"#
+    )
+    .bind(id)
+    .bind(payload.into)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("DELETE FROM blog_post_tags WHERE tag_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE blog_tag_slug_history SET tag_id = $2 WHERE tag_id = $1")
+        .bind(id)
+        .bind(payload.into)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM blog_tags WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    record_old_slug(&mut tx, &source_slug, payload.into).await?;
+
+    let tag = sqlx::query_as::<_, BlogTag>("SELECT id, name, slug, created_at, updated_at FROM blog_tags WHERE id = $1")
+        .bind(payload.into)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    state.invalidate_posts().await;
+
+    Ok(Json(MergeTagResponse { success: true, tag, moved_posts: moved }))
+}
+
+#[derive(Deserialize)]
+pub struct BulkTagPayload {
+    #[serde(default)]
+    pub add: Vec<i32>,
+    #[serde(default)]
+    pub remove: Vec<i32>,
+}
+
+#[derive(Serialize)]
+pub struct BulkTagResponse {
+    pub success: bool,
+    /// Posts that didn't have the tag before
+    pub added: u64,
+    /// Posts that had the tag before
+    pub removed: u64,
+}
+
+/// Message for the 400 when a bulk request is malformed, or `None` if it's fine.
+fn bulk_problem(payload: &BulkTagPayload) -> Option<String> {
+    if payload.add.is_empty() && payload.remove.is_empty() {
+        return Some("Nothing to do: give post ids in `add` and/or `remove`".to_string());
+    }
+    let mut both: Vec<i32> = payload.add.iter().copied().filter(|id| payload.remove.contains(id)).collect();
+    both.sort_unstable();
+    both.dedup();
+    if !both.is_empty() {
+        let ids: Vec<String> = both.iter().map(|id| id.to_string()).collect();
+        return Some(format!("Post ids in both add and remove: {}", ids.join(", ")));
+    }
+    None
+}
+
+// POST /api/blog/admin/tags/:id/posts - Add the tag to and/or remove it from many posts.
+pub async fn bulk_tag_posts(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(payload): Json<BulkTagPayload>,
+) -> Result<Json<BulkTagResponse>, AppError> {
+    if let Some(message) = bulk_problem(&payload) {
+        return Err(AppError::ValidationError(message));
+    }
+
+    let mut tx = state.pool.begin().await?;
+    lock_tag(&mut tx, id).await?;
+
+    let requested: Vec<i32> = payload.add.iter().chain(&payload.remove).copied().collect();
+    let unknown = sqlx::query_scalar::<_, i32>(
+        "SELECT DISTINCT u.id FROM UNNEST($1::int[]) AS u(id) WHERE NOT EXISTS (SELECT 1 FROM blog_posts p WHERE p.id = u.id) ORDER BY u.id"
+    )
+    .bind(&requested)
+    .fetch_all(&mut *tx)
+    .await?;
+    if !unknown.is_empty() {
+        let ids: Vec<String> = unknown.iter().map(|id| id.to_string()).collect();
+        return Err(AppError::ValidationError(format!("Unknown post ids: {}", ids.join(", "))));
+    }
+
+    let added = sqlx::query(
+        r#"
+INSERT INTO blog_post_tags (post_id, tag_id)
+SELECT DISTINCT u.id, $2 FROM UNNEST($1::int[]) AS u(id)
+WHERE NOT EXISTS (SELECT 1 FROM blog_post_tags pt WHERE pt.post_id = u.id AND pt.tag_id = $2)
+"#
+    )
+    .bind(&payload.add)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let removed = sqlx::query("DELETE FROM blog_post_tags WHERE tag_id = $1 AND post_id = ANY($2)")
+        .bind(id)
+        .bind(&payload.remove)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+
+    state.invalidate_posts().await;
+
+    Ok(Json(BulkTagResponse { success: true, added, removed }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_problem() {
+        let payload = |add: Vec<i32>, remove: Vec<i32>| BulkTagPayload { add, remove };
+
+        assert!(bulk_problem(&payload(vec![1, 2], vec![3])).is_none());
+        assert!(bulk_problem(&payload(vec![], vec![])).is_some());
+        assert_eq!(
+            bulk_problem(&payload(vec![5, 2, 5], vec![5, 2])).unwrap(),
+            "Post ids in both add and remove: 2, 5"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{State, Path},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use crate::i18n::{TranslatableKind, TranslationMap};
+use crate::models::Translation;
+use crate::error::AppError;
+use crate::highlight;
+use crate::state::AppState;
+
+#[derive(Deserialize, Debug, Default)]
+pub struct LangQuery {
+    /// Requested locale; takes precedence over `Accept-Language`
+    pub lang: Option<String>,
+}
+
+/// Every translation of one kind, cached until a translation of that kind changes.
+pub(crate) async fn load(state: &AppState, kind: TranslatableKind) -> Result<Arc<TranslationMap>, AppError> {
+    let cache_key = kind.name().to_string();
+    if let Some(cached) = state.translations_cache.get(&cache_key).await {
+        return Ok(cached);
+    }
+
+    let rows = sqlx::query_as::<_, (i32, String, serde_json::Value)>(&format!(
+        "SELECT item_id, locale, fields FROM {}",
+        kind.translation_table()
+    ))
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut map = TranslationMap::new();
+    for (item_id, locale, fields) in rows {
+        let fields = fields
+            .as_object()
+            .map(|object| {
+                object
+                    .iter()
+                    .filter_map(|(field, value)| value.as_str().map(|text| (field.clone(), text.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        map.entry(item_id).or_default().insert(locale, fields);
+    }
+
+    let map = Arc::new(map);
+    state.translations_cache.insert(cache_key, map.clone()).await;
+    Ok(map)
+}
+
+fn parse_kind(entity: &str) -> Result<TranslatableKind, AppError> {
+    TranslatableKind::parse(entity)
+        .ok_or_else(|| AppError::ValidationError(format!("Unknown entity: {}", entity)))
+}
+
+/// Check a translation body against the fields `kind` allows, returning the message for the 400.
+pub(crate) fn validate_fields(kind: TranslatableKind, fields: &HashMap<String, String>) -> Result<(), String> {
+    if fields.is_empty() {
+        return Err("At least one field is required".to_string());
+    }
+
+    let mut unknown: Vec<&str> = fields
+        .keys()
+        .map(String::as_str)
+        .filter(|field| !kind.fields().contains(field))
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort_unstable();
+        return Err(format!(
+            "Unknown fields for {}: {} (allowed: {})",
+            kind.name(),
+            unknown.join(", "),
+            kind.fields().join(", ")
+        ));
+    }
+
+    let mut empty: Vec<&str> = fields
+        .iter()
+        .filter(|(_, value)| value.trim().is_empty())
+        .map(|(field, _)| field.as_str())
+        .collect();
+    if !empty.is_empty() {
+        empty.sort_unstable();
+        return Err(format!("Empty fields: {}", empty.join(", ")));
+    }
+
+    Ok(())
+}
+
+async fn ensure_item_exists(state: &AppState, kind: TranslatableKind, id: i32) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+        kind.base_table()
+    ))
+    .bind(id)
+    .fetch_one(&state.pool)
+    .await?;
+
+    if exists { Ok(()) } else { Err(AppError::NotFound) }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/translations/{entity}/{id}",
+    params(
+        ("entity" = String, Path, description = "posts, about, skills, projects or experience"),
+        ("id" = i32, Path, description = "Item ID")
+    ),
+    responses(
+        (status = 200, description = "Translations of the item, by locale", body = [Translation]),
+        (status = 400, description = "Unknown entity"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_translations(
+    Path((entity, id)): Path<(String, i32)>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Translation>>, AppError> {
+    let kind = parse_kind(&entity)?;
+    ensure_item_exists(&state, kind, id).await?;
+
+    let translations = sqlx::query_as::<_, Translation>(&format!(
+        "SELECT locale, fields, updated_at FROM {} WHERE item_id = $1 ORDER BY locale ASC",
+        kind.translation_table()
+    ))
+    .bind(id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(translations))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TranslationPayload {
+    /// Field name → translated text; replaces any previous translation for the locale
+    pub fields: HashMap<String, String>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/translations/{entity}/{id}/{locale}",
+    params(
+        ("entity" = String, Path, description = "posts, about, skills, projects or experience"),
+        ("id" = i32, Path, description = "Item ID"),
+        ("locale" = String, Path, description = "Locale other than the site default")
+    ),
+    request_body = TranslationPayload,
+    responses(
+        (status = 200, description = "Translation saved", body = Translation),
+        (status = 400, description = "Unknown entity, unsupported locale or invalid fields"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn put_translation(
+    Path((entity, id, locale)): Path<(String, i32, String)>,
+    State(state): State<AppState>,
+    Json(mut payload): Json<TranslationPayload>,
+) -> Result<Json<Translation>, AppError> {
+    let kind = parse_kind(&entity)?;
+    let locale = locale.to_lowercase();
+
+    if locale == state.site.language {
+        return Err(AppError::ValidationError(format!(
+            "{} is the default locale; edit the item itself",
+            locale
+        )));
+    }
+    if !state.site.locales.contains(&locale) {
+        return Err(AppError::ValidationError(format!(
+            "Unsupported locale: {} (supported: {})",
+            locale,
+            state.site.locales.join(", ")
+        )));
+    }
+    validate_fields(kind, &payload.fields).map_err(AppError::ValidationError)?;
+    ensure_item_exists(&state, kind, id).await?;
+    if kind == TranslatableKind::Posts {
+        if let Some(content) = payload.fields.get_mut("content") {
+            *content = highlight::highlight_html(content);
+        }
+    }
+
+    let translation = sqlx::query_as::<_, Translation>(&format!(
+        r#"
+INSERT INTO {} (item_id, locale, fields) VALUES ($1, $2, $3)
+ON CONFLICT (item_id, locale) DO UPDATE SET fields = EXCLUDED.fields, updated_at = NOW()
+RETURNING locale, fields, updated_at
+"#,
+        kind.translation_table()
+    ))
+    .bind(id)
+    .bind(&locale)
+    .bind(serde_json::json!(payload.fields))
+    .fetch_one(&state.pool)
+    .await?;
+
+    state.translations_cache.invalidate(&kind.name().to_string()).await;
+
+    Ok(Json(translation))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/translations/{entity}/{id}/{locale}",
+    params(
+        ("entity" = String, Path, description = "posts, about, skills, projects or experience"),
+        ("id" = i32, Path, description = "Item ID"),
+        ("locale" = String, Path, description = "Locale")
+    ),
+    responses(
+        (status = 200, description = "Translation deleted", body = serde_json::Value),
+        (status = 400, description = "Unknown entity"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn delete_translation(
+    Path((entity, id, locale)): Path<(String, i32, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let kind = parse_kind(&entity)?;
+
+    let result = sqlx::query(&format!(
+        "DELETE FROM {} WHERE item_id = $1 AND locale = $2",
+        kind.translation_table()
+    ))
+    .bind(id)
+    .bind(locale.to_lowercase())
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+
+    state.translations_cache.invalidate(&kind.name().to_string()).await;
+
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        assert!(validate_fields(TranslatableKind::Skills, &fields(&[("title", "รัสต์")])).is_ok());
+        assert!(validate_fields(TranslatableKind::Skills, &HashMap::new()).is_err());
+
+        let err = validate_fields(TranslatableKind::Projects, &fields(&[("title", "x"), ("link", "y"), ("image", "z")])).unwrap_err();
+        assert_eq!(err, "Unknown fields for projects: image, link (allowed: title, description, role, case_study)");
+
+        let err = validate_fields(TranslatableKind::About, &fields(&[("user_bio", "  ")])).unwrap_err();
+        assert_eq!(err, "Empty fields: user_bio");
+    }
+}
@@ -0,0 +1,442 @@
+use crate::feeds::escape_xml;
+use crate::toc::decode_entities;
+
+/// Token classes emitted as `<span class="hl-…">`; the theme CSS styles these.
+const KEYWORD: &str = "keyword";
+const TYPE: &str = "type";
+const STRING: &str = "string";
+const NUMBER: &str = "number";
+const COMMENT: &str = "comment";
+const FUNCTION: &str = "function";
+const MACRO: &str = "macro";
+const ATTRIBUTE: &str = "attribute";
+const LIFETIME: &str = "lifetime";
+const VARIABLE: &str = "variable";
+const KEY: &str = "key";
+const SECTION: &str = "section";
+const LITERAL: &str = "literal";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Language {
+    Rust,
+    TypeScript,
+    Sql,
+    Shell,
+    Toml,
+}
+
+impl Language {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Some(Language::Rust),
+            "typescript" | "ts" | "tsx" | "javascript" | "js" | "jsx" => Some(Language::TypeScript),
+            "sql" | "postgres" | "postgresql" | "pgsql" => Some(Language::Sql),
+            "shell" | "sh" | "bash" | "zsh" | "console" => Some(Language::Shell),
+            "toml" => Some(Language::Toml),
+            _ => None,
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+                "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+                "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use", "where",
+                "while",
+            ],
+            Language::TypeScript => &[
+                "abstract", "as", "async", "await", "break", "case", "catch", "class", "const", "continue",
+                "default", "delete", "do", "else", "enum", "export", "extends", "finally", "for", "from",
+                "function", "if", "implements", "import", "in", "instanceof", "interface", "let", "new", "of",
+                "private", "protected", "public", "readonly", "return", "static", "super", "switch", "this",
+                "throw", "try", "type", "typeof", "var", "void", "while", "yield",
+            ],
+            Language::Sql => &[
+                "add", "all", "alter", "and", "as", "asc", "begin", "between", "by", "cascade", "case", "check",
+                "column", "commit", "conflict", "constraint", "create", "cross", "default", "delete", "desc",
+                "distinct", "do", "drop", "else", "end", "exists", "foreign", "from", "full", "group", "having",
+                "if", "in", "index", "inner", "insert", "into", "is", "join", "key", "lateral", "left", "like",
+                "limit", "not", "nothing", "offset", "on", "or", "order", "outer", "over", "partition", "primary",
+                "references", "returning", "right", "rollback", "select", "set", "table", "then", "union",
+                "unique", "update", "using", "values", "when", "where", "window", "with",
+            ],
+            Language::Shell => &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in",
+                "local", "readonly", "return", "then", "until", "while",
+            ],
+            Language::Toml => &[],
+        }
+    }
+
+    fn types(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &[
+                "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8", "u16",
+                "u32", "u64", "u128", "usize",
+            ],
+            Language::TypeScript => &["any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown"],
+            Language::Sql => &[
+                "bigint", "boolean", "char", "date", "int", "integer", "json", "jsonb", "numeric", "serial",
+                "smallint", "text", "timestamp", "timestamptz", "uuid", "varchar",
+            ],
+            Language::Shell | Language::Toml => &[],
+        }
+    }
+
+    fn literals(self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &["true", "false", "None", "Some", "Ok", "Err"],
+            Language::TypeScript => &["true", "false", "null", "undefined", "NaN", "Infinity"],
+            Language::Sql => &["true", "false", "null"],
+            Language::Shell => &["true", "false"],
+            Language::Toml => &["true", "false", "inf", "nan"],
+        }
+    }
+
+    fn line_comment(self) -> &'static str {
+        match self {
+            Language::Rust | Language::TypeScript => "//",
+            Language::Sql => "--",
+            Language::Shell | Language::Toml => "#",
+        }
+    }
+
+    fn block_comment(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Language::Rust | Language::TypeScript | Language::Sql => Some(("/*", "*/")),
+            Language::Shell | Language::Toml => None,
+        }
+    }
+
+    fn quotes(self) -> &'static [char] {
+        match self {
+            Language::TypeScript => &['"', '\'', '`'],
+            Language::Rust => &['"'],
+            Language::Sql | Language::Shell | Language::Toml => &['"', '\''],
+        }
+    }
+
+    fn case_insensitive(self) -> bool {
+        self == Language::Sql
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length in bytes of the prefix of `s` whose chars satisfy `f`.
+fn take_while(s: &str, f: impl Fn(char) -> bool) -> usize {
+    s.char_indices().find(|&(_, c)| !f(c)).map(|(i, _)| i).unwrap_or(s.len())
+}
+
+/// Length of a quoted string starting at `s[0]`, honouring backslash escapes
+/// (except in SQL, where quotes are doubled instead). Unterminated strings run to the end.
+fn string_len(s: &str, lang: Language) -> usize {
+    let quote = s.chars().next().unwrap();
+    if lang == Language::Toml {
+        let triple: String = std::iter::repeat_n(quote, 3).collect();
+        if let Some(body) = s.strip_prefix(triple.as_str()) {
+            return body.find(triple.as_str()).map(|i| i + 6).unwrap_or(s.len());
+        }
+    }
+
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && lang != Language::Sql && !(lang == Language::Shell && quote == '\'') {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    s.len()
+}
+
+fn push_span(out: &mut String, class: &str, text: &str) {
+    out.push_str(&format!("<span class=\"hl-{}\">{}</span>", class, escape_xml(text)));
+}
+
+fn at_line_start(code: &str, pos: usize) -> bool {
+    code[..pos].rsplit('\n').next().map(|line| line.trim().is_empty()).unwrap_or(true)
+}
+
+fn highlight(code: &str, lang: Language) -> String {
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut pos = 0;
+
+    while pos < code.len() {
+        let rest = &code[pos..];
+        let c = rest.chars().next().unwrap();
+
+        if let Some((open, close)) = lang.block_comment() {
+            if let Some(body) = rest.strip_prefix(open) {
+                let len = body.find(close).map(|i| i + open.len() + close.len()).unwrap_or(rest.len());
+                push_span(&mut out, COMMENT, &rest[..len]);
+                pos += len;
+                continue;
+            }
+        }
+
+        let line_comment = lang.line_comment();
+        let comment_allowed = lang != Language::Shell || pos == 0 || code[..pos].ends_with(char::is_whitespace);
+        if rest.starts_with(line_comment) && comment_allowed {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            push_span(&mut out, COMMENT, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if lang == Language::Rust && c == '\'' {
+            // `'a` is a lifetime unless it closes like a char literal (`'a'`).
+            let ident = take_while(&rest[1..], is_ident_char);
+            if ident > 0 && !rest[1 + ident..].starts_with('\'') {
+                push_span(&mut out, LIFETIME, &rest[..1 + ident]);
+                pos += 1 + ident;
+                continue;
+            }
+            let len = string_len(rest, lang);
+            push_span(&mut out, STRING, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if lang.quotes().contains(&c) {
+            let len = string_len(rest, lang);
+            push_span(&mut out, STRING, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if lang == Language::Rust && (rest.starts_with("#[") || rest.starts_with("#![")) {
+            let len = rest.find(']').map(|i| i + 1).unwrap_or(rest.len());
+            push_span(&mut out, ATTRIBUTE, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if lang == Language::Toml && c == '[' && at_line_start(code, pos) {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            let len = rest[..len].rfind(']').map(|i| i + 1).unwrap_or(len);
+            push_span(&mut out, SECTION, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if lang == Language::Shell && c == '$' {
+            let len = if rest[1..].starts_with('{') {
+                rest.find('}').map(|i| i + 1).unwrap_or(rest.len())
+            } else if rest[1..].starts_with(|c: char| "@#?*!$0123456789".contains(c)) {
+                2
+            } else {
+                1 + take_while(&rest[1..], is_ident_char)
+            };
+            if len > 1 {
+                push_span(&mut out, VARIABLE, &rest[..len]);
+                pos += len;
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() {
+            let len = take_while(rest, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            push_span(&mut out, NUMBER, &rest[..len]);
+            pos += len;
+            continue;
+        }
+
+        if is_ident_start(c) {
+            let ident_chars = |c: char| is_ident_char(c) || (lang == Language::Toml && c == '-');
+            let len = take_while(rest, ident_chars);
+            let word = &rest[..len];
+            let after = &rest[len..];
+            let next = after.trim_start_matches([' ', '\t']).chars().next();
+
+            let matches = |list: &[&str]| {
+                if lang.case_insensitive() {
+                    list.iter().any(|k| k.eq_ignore_ascii_case(word))
+                } else {
+                    list.contains(&word)
+                }
+            };
+
+            let class = if lang == Language::Toml && next == Some('=') {
+                Some(KEY)
+            } else if matches(lang.keywords()) {
+                Some(KEYWORD)
+            } else if matches(lang.literals()) {
+                Some(LITERAL)
+            } else if matches(lang.types()) {
+                Some(TYPE)
+            } else if lang == Language::Rust && after.starts_with('!') && !after.starts_with("!=") {
+                Some(MACRO)
+            } else if matches!(lang, Language::Rust | Language::TypeScript | Language::Sql) && next == Some('(') {
+                Some(FUNCTION)
+            } else if matches!(lang, Language::Rust | Language::TypeScript) && word.starts_with(char::is_uppercase) {
+                Some(TYPE)
+            } else if lang == Language::Shell && at_line_start(code, pos) {
+                Some(FUNCTION)
+            } else {
+                None
+            };
+
+            let len = if class == Some(MACRO) { len + 1 } else { len };
+            match class {
+                Some(class) => push_span(&mut out, class, &rest[..len]),
+                None => out.push_str(&escape_xml(word)),
+            }
+            pos += len;
+            continue;
+        }
+
+        out.push_str(&escape_xml(&rest[..c.len_utf8()]));
+        pos += c.len_utf8();
+    }
+
+    out
+}
+
+/// Language named by a `language-xxx` / `lang-xxx` class in a `<code …>` opening tag.
+fn code_language(open_tag: &str) -> Option<Language> {
+    let lower = open_tag.to_ascii_lowercase();
+    let class_at = lower.find("class=")?;
+    let value = &lower[class_at + 6..];
+    let quote = value.chars().next().filter(|q| *q == '"' || *q == '\'')?;
+    let classes = value[1..].split(quote).next()?;
+    classes.split_whitespace().find_map(|class| {
+        class
+            .strip_prefix("language-")
+            .or_else(|| class.strip_prefix("lang-"))
+            .and_then(Language::from_name)
+    })
+}
+
+/// Highlight every `<code class="language-…">` block of a supported language.
+/// Blocks that already contain markup (highlighted before) are left alone.
+pub fn highlight_html(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len() + html.len() / 2);
+    let mut copied = 0;
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("<code") {
+        let start = pos + found;
+
+        let Some(open_end) = lower[start..].find('>').map(|i| start + i) else { break };
+        let Some(close) = lower[open_end..].find("</code>").map(|i| open_end + i) else { break };
+        pos = close;
+
+        let Some(lang) = code_language(&html[start..open_end]) else { continue };
+        let inner = &html[open_end + 1..close];
+        if inner.contains('<') {
+            continue;
+        }
+
+        out.push_str(&html[copied..=open_end]);
+        out.push_str(&highlight(&decode_entities(inner), lang));
+        copied = close;
+    }
+
+    out.push_str(&html[copied..]);
+    out
+}
+
+const LIGHT_THEME: &str = r#"/* Syntax highlighting: light */
+.hl-keyword { color: #d73a49; }
+.hl-type { color: #6f42c1; }
+.hl-string { color: #032f62; }
+.hl-number, .hl-literal { color: #005cc5; }
+.hl-comment { color: #6a737d; font-style: italic; }
+.hl-function { color: #6f42c1; }
+.hl-macro { color: #e36209; }
+.hl-attribute { color: #22863a; }
+.hl-lifetime { color: #e36209; font-style: italic; }
+.hl-variable { color: #e36209; }
+.hl-key { color: #005cc5; }
+.hl-section { color: #22863a; font-weight: bold; }
+"#;
+
+const DARK_THEME: &str = r#"/* Syntax highlighting: dark */
+.hl-keyword { color: #ff7b72; }
+.hl-type { color: #d2a8ff; }
+.hl-string { color: #a5d6ff; }
+.hl-number, .hl-literal { color: #79c0ff; }
+.hl-comment { color: #8b949e; font-style: italic; }
+.hl-function { color: #d2a8ff; }
+.hl-macro { color: #ffa657; }
+.hl-attribute { color: #7ee787; }
+.hl-lifetime { color: #ffa657; font-style: italic; }
+.hl-variable { color: #ffa657; }
+.hl-key { color: #79c0ff; }
+.hl-section { color: #7ee787; font-weight: bold; }
+"#;
+
+/// Stylesheet for the `hl-*` classes. Unknown themes fall back to light.
+pub fn theme_css(theme: &str) -> &'static str {
+    match theme {
+        "dark" => DARK_THEME,
+        _ => LIGHT_THEME,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_rust() {
+        let html = highlight("#[derive(Debug)]\nfn main() { println!(\"hi\"); let x: u32 = 42; } // done\nimpl<'a> Wrapper<'a> {}", Language::Rust);
+        assert!(html.contains("<span class=\"hl-attribute\">#[derive(Debug)]</span>"));
+        assert!(html.contains("<span class=\"hl-keyword\">fn</span> <span class=\"hl-function\">main</span>()"));
+        assert!(html.contains("<span class=\"hl-type\">Wrapper</span>&lt;<span class=\"hl-lifetime\">&apos;a</span>&gt;"));
+        assert!(html.contains("<span class=\"hl-macro\">println!</span>(<span class=\"hl-string\">&quot;hi&quot;</span>)"));
+        assert!(html.contains("<span class=\"hl-type\">u32</span> = <span class=\"hl-number\">42</span>"));
+        assert!(html.contains("<span class=\"hl-comment\">// done</span>"));
+    }
+
+    #[test]
+    fn test_highlight_sql_is_case_insensitive() {
+        let html = highlight("SELECT id FROM posts WHERE slug = 'it''s' -- note", Language::Sql);
+        assert!(html.starts_with("<span class=\"hl-keyword\">SELECT</span> id <span class=\"hl-keyword\">FROM</span>"));
+        assert!(html.contains("<span class=\"hl-string\">&apos;it&apos;</span><span class=\"hl-string\">&apos;s&apos;</span>"));
+        assert!(html.ends_with("<span class=\"hl-comment\">-- note</span>"));
+    }
+
+    #[test]
+    fn test_highlight_shell_and_toml() {
+        let shell = highlight("export PORT=8080 # comment\necho \"$HOME\" ${USER}", Language::Shell);
+        assert!(shell.contains("<span class=\"hl-keyword\">export</span>"));
+        assert!(shell.contains("<span class=\"hl-comment\"># comment</span>"));
+        assert!(shell.contains("<span class=\"hl-variable\">${USER}</span>"));
+
+        let toml = highlight("[dependencies]\naxum = { version = \"0.7\" }", Language::Toml);
+        assert!(toml.contains("<span class=\"hl-section\">[dependencies]</span>"));
+        assert!(toml.contains("<span class=\"hl-key\">axum</span>"));
+        assert!(toml.contains("<span class=\"hl-string\">&quot;0.7&quot;</span>"));
+    }
+
+    #[test]
+    fn test_highlight_html_code_blocks() {
+        let html = "<p>Run:</p><pre><code class=\"language-ts\">const x: number = 1 &lt; 2;</code></pre><pre><code class=\"language-haskell\">main = 1</code></pre><code>plain</code>";
+        let out = highlight_html(html);
+        assert!(out.starts_with("<p>Run:</p><pre><code class=\"language-ts\"><span class=\"hl-keyword\">const</span> x: <span class=\"hl-type\">number</span>"));
+        assert!(out.contains("<span class=\"hl-number\">1</span> &lt; <span class=\"hl-number\">2</span>;</code></pre>"));
+        assert!(out.contains("<code class=\"language-haskell\">main = 1</code>"));
+        assert!(out.ends_with("<code>plain</code>"));
+
+        // Already highlighted blocks are not touched again.
+        assert_eq!(highlight_html(&out), out);
+    }
+
+    #[test]
+    fn test_theme_css() {
+        assert!(theme_css("dark").contains(".hl-keyword"));
+        assert_eq!(theme_css("unknown"), theme_css("light"));
+    }
+}
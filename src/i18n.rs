@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::config::SiteConfig;
+use crate::models::{About, BlogPost, ExperienceTimeline, Project, Skill};
+
+/// item id → locale → field → translated text
+pub type TranslationMap = HashMap<i32, HashMap<String, HashMap<String, String>>>;
+
+/// Content types that can carry translations, named as in the admin API path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranslatableKind {
+    Posts,
+    About,
+    Skills,
+    Projects,
+    Experience,
+}
+
+impl TranslatableKind {
+    pub fn parse(entity: &str) -> Option<Self> {
+        match entity {
+            "posts" => Some(Self::Posts),
+            "about" => Some(Self::About),
+            "skills" => Some(Self::Skills),
+            "projects" => Some(Self::Projects),
+            "experience" => Some(Self::Experience),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Posts => "posts",
+            Self::About => "about",
+            Self::Skills => "skills",
+            Self::Projects => "projects",
+            Self::Experience => "experience",
+        }
+    }
+
+    /// Table holding the default-locale rows.
+    pub fn base_table(self) -> &'static str {
+        match self {
+            Self::Posts => "blog_posts",
+            Self::About => "about",
+            Self::Skills => "skills",
+            Self::Projects => "projects",
+            Self::Experience => "experience_timeline",
+        }
+    }
+
+    pub fn translation_table(self) -> &'static str {
+        match self {
+            Self::Posts => "blog_post_translations",
+            Self::About => "about_translations",
+            Self::Skills => "skill_translations",
+            Self::Projects => "project_translations",
+            Self::Experience => "experience_translations",
+        }
+    }
+
+    /// Text fields a translation may override.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Posts => &["title", "excerpt", "content", "content_markdown", "meta_title", "meta_description"],
+            Self::About => &["user_bio", "user_bio2"],
+            Self::Skills => &["title", "description"],
+            Self::Projects => &["title", "description", "role", "case_study"],
+            Self::Experience => &["title", "company", "period", "description"],
+        }
+    }
+}
+
+/// A model whose text fields can be overlaid with a translation.
+pub trait Translatable {
+    fn id(&self) -> i32;
+    fn set_field(&mut self, field: &str, value: String);
+    fn set_locales(&mut self, locale: String, available: Vec<String>);
+}
+
+impl Translatable for BlogPost {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "title" => self.title = value,
+            "excerpt" => self.excerpt = value,
+            "content" => self.content = value,
+            "content_markdown" => self.content_markdown = Some(value),
+            "meta_title" => self.meta_title = Some(value),
+            "meta_description" => self.meta_description = Some(value),
+            _ => {}
+        }
+    }
+
+    fn set_locales(&mut self, locale: String, available: Vec<String>) {
+        self.locale = Some(locale);
+        self.available_locales = Some(available);
+    }
+}
+
+impl Translatable for About {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "user_bio" => self.user_bio = value,
+            "user_bio2" => self.user_bio2 = value,
+            _ => {}
+        }
+    }
+
+    fn set_locales(&mut self, locale: String, available: Vec<String>) {
+        self.locale = Some(locale);
+        self.available_locales = Some(available);
+    }
+}
+
+impl Translatable for Skill {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "title" => self.title = value,
+            "description" => self.description = value,
+            _ => {}
+        }
+    }
+
+    fn set_locales(&mut self, locale: String, available: Vec<String>) {
+        self.locale = Some(locale);
+        self.available_locales = Some(available);
+    }
+}
+
+impl Translatable for Project {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "title" => self.title = value,
+            "description" => self.description = value,
+            "role" => self.role = Some(value),
+            "case_study" => self.case_study = Some(value),
+            _ => {}
+        }
+    }
+
+    fn set_locales(&mut self, locale: String, available: Vec<String>) {
+        self.locale = Some(locale);
+        self.available_locales = Some(available);
+    }
+}
+
+impl Translatable for ExperienceTimeline {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_field(&mut self, field: &str, value: String) {
+        match field {
+            "title" => self.title = value,
+            "company" => self.company = value,
+            "period" => self.period = value,
+            "description" => self.description = value,
+            _ => {}
+        }
+    }
+
+    fn set_locales(&mut self, locale: String, available: Vec<String>) {
+        self.locale = Some(locale);
+        self.available_locales = Some(available);
+    }
+}
+
+/// Supported locale matching `tag` exactly or by primary subtag (`th-TH` → `th`).
+fn match_locale(tag: &str, site: &SiteConfig) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    let primary = tag.split(['-', '_']).next().unwrap_or_default();
+    site.locales
+        .iter()
+        .find(|l| **l == tag)
+        .or_else(|| site.locales.iter().find(|l| *l == primary))
+        .cloned()
+}
+
+/// Locale to serve: `?lang=` if supported, else the best `Accept-Language` match,
+/// else the site default.
+pub fn negotiate(lang: Option<&str>, accept_language: Option<&str>, site: &SiteConfig) -> String {
+    if let Some(locale) = lang.and_then(|l| match_locale(l, site)) {
+        return locale;
+    }
+
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let q = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*" && q > 0.0).then_some((tag, q))
+        })
+        .collect();
+    // Stable sort keeps header order among equal weights.
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .find_map(|(tag, _)| match_locale(tag, site))
+        .unwrap_or_else(|| site.language.clone())
+}
+
+/// Locale negotiated from a request's `?lang=` value and headers.
+pub fn request_locale(lang: Option<&str>, headers: &HeaderMap, site: &SiteConfig) -> String {
+    let accept = headers.get(header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+    negotiate(lang, accept, site)
+}
+
+/// Apply the `locale` translation of `item`, if there is one, and record which
+/// locale was served and which ones exist. Missing fields keep the default text.
+pub fn localize<T: Translatable>(item: &mut T, translations: &TranslationMap, locale: &str, site: &SiteConfig) {
+    let item_translations = translations.get(&item.id());
+    let has = |l: &str| item_translations.is_some_and(|t| t.contains_key(l));
+
+    let available: Vec<String> = site
+        .locales
+        .iter()
+        .filter(|l| **l == site.language || has(l))
+        .cloned()
+        .collect();
+
+    let served = match item_translations.and_then(|t| t.get(locale)) {
+        Some(fields) if locale != site.language => {
+            for (field, value) in fields {
+                item.set_field(field, value.clone());
+            }
+            locale.to_string()
+        }
+        _ => site.language.clone(),
+    };
+
+    item.set_locales(served, available);
+}
+
+/// Swap the `title` of a `{id, title, ...}` link to another item for its `locale`
+/// translation, if there is one.
+pub fn localize_link_title(link: &mut serde_json::Value, translations: &TranslationMap, locale: &str) {
+    let title = link["id"]
+        .as_i64()
+        .and_then(|id| translations.get(&(id as i32)))
+        .and_then(|t| t.get(locale))
+        .and_then(|fields| fields.get("title"));
+    if let (Some(title), Some(link)) = (title, link.as_object_mut()) {
+        link.insert("title".to_string(), serde_json::Value::String(title.clone()));
+    }
+}
+
+/// `Link` header value listing each available locale of the page at `url`,
+/// plus `x-default`. Non-default locales are addressed with `?lang=`.
+pub fn alternate_links(url: &str, available: &[String], site: &SiteConfig) -> String {
+    let href = |locale: &str| {
+        if locale == site.language {
+            url.to_string()
+        } else {
+            format!("{}?lang={}", url, locale)
+        }
+    };
+
+    available
+        .iter()
+        .map(|locale| format!("<{}>; rel=\"alternate\"; hreflang=\"{}\"", href(locale), locale))
+        .chain(std::iter::once(format!("<{}>; rel=\"alternate\"; hreflang=\"x-default\"", url)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// JSON response tagged with the served locale. Responses vary by `Accept-Language`,
+/// so shared caches must key on it.
+pub fn localized_json<T: Serialize>(body: T, locale: &str) -> Response {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(locale) {
+        headers.insert(header::CONTENT_LANGUAGE, value);
+    }
+    headers.insert(header::VARY, HeaderValue::from_static("Accept-Language"));
+    (headers, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SitemapConfig;
+
+    fn site() -> SiteConfig {
+        SiteConfig {
+            base_url: "https://pasu.app".to_string(),
+            api_url: "https://api.pasu.app".to_string(),
+            title: "PASU.APP".to_string(),
+            description: "Blog".to_string(),
+            language: "en".to_string(),
+            locales: vec!["en".to_string(), "th".to_string()],
+            author: "Pasu".to_string(),
+            blog_path: "/blog".to_string(),
+            feed_full_content: false,
+            feed_limit: 20,
+            sitemap: SitemapConfig::default(),
+        }
+    }
+
+    fn skill(id: i32) -> Skill {
+        Skill {
+            id,
+            icon: "rust".to_string(),
+            title: "Rust".to_string(),
+            description: "Systems programming".to_string(),
+            created_at: None,
+            updated_at: None,
+            categories: None,
+            tags: None,
+            locale: None,
+            available_locales: None,
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let site = site();
+        assert_eq!(negotiate(Some("th"), Some("en-US"), &site), "th");
+        assert_eq!(negotiate(Some("fr"), Some("th-TH,en;q=0.8"), &site), "th");
+        assert_eq!(negotiate(None, Some("fr;q=1, en;q=0.5, th;q=0.9"), &site), "th");
+        assert_eq!(negotiate(None, Some("TH_th"), &site), "th");
+        assert_eq!(negotiate(None, Some("th;q=0, *"), &site), "en");
+        assert_eq!(negotiate(None, None, &site), "en");
+    }
+
+    #[test]
+    fn test_localize_overlays_fields() {
+        let site = site();
+        let mut translations = TranslationMap::new();
+        translations.entry(1).or_default().insert(
+            "th".to_string(),
+            HashMap::from([("title".to_string(), "รัสต์".to_string())]),
+        );
+
+        let mut translated = skill(1);
+        localize(&mut translated, &translations, "th", &site);
+        assert_eq!(translated.title, "รัสต์");
+        assert_eq!(translated.description, "Systems programming");
+        assert_eq!(translated.locale.as_deref(), Some("th"));
+        assert_eq!(translated.available_locales, Some(vec!["en".to_string(), "th".to_string()]));
+
+        // No Thai row for this one: falls back to the default text.
+        let mut untranslated = skill(2);
+        localize(&mut untranslated, &translations, "th", &site);
+        assert_eq!(untranslated.title, "Rust");
+        assert_eq!(untranslated.locale.as_deref(), Some("en"));
+        assert_eq!(untranslated.available_locales, Some(vec!["en".to_string()]));
+    }
+
+    #[test]
+    fn test_localize_link_title() {
+        let mut translations = TranslationMap::new();
+        translations.entry(4).or_default().insert(
+            "th".to_string(),
+            HashMap::from([("title".to_string(), "ตอนก่อน".to_string())]),
+        );
+
+        let mut link = serde_json::json!({ "id": 4, "title": "Previous", "slug": "previous" });
+        localize_link_title(&mut link, &translations, "en");
+        assert_eq!(link["title"], "Previous");
+        localize_link_title(&mut link, &translations, "th");
+        assert_eq!(link["title"], "ตอนก่อน");
+        assert_eq!(link["slug"], "previous");
+    }
+
+    #[test]
+    fn test_alternate_links() {
+        let site = site();
+        let links = alternate_links("https://pasu.app/blog/hello", &["en".to_string(), "th".to_string()], &site);
+        assert_eq!(
+            links,
+            "<https://pasu.app/blog/hello>; rel=\"alternate\"; hreflang=\"en\", \
+             <https://pasu.app/blog/hello?lang=th>; rel=\"alternate\"; hreflang=\"th\", \
+             <https://pasu.app/blog/hello>; rel=\"alternate\"; hreflang=\"x-default\""
+        );
+    }
+
+    #[test]
+    fn test_kind_fields() {
+        assert_eq!(TranslatableKind::parse("experience"), Some(TranslatableKind::Experience));
+        assert_eq!(TranslatableKind::parse("contact"), None);
+        assert!(TranslatableKind::Posts.fields().contains(&"content_markdown"));
+        assert!(!TranslatableKind::Projects.fields().contains(&"link"));
+    }
+}
@@ -0,0 +1,263 @@
+use std::io::{Cursor, Read};
+
+use chrono::NaiveDateTime;
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::frontmatter::{self, FrontMatter};
+use crate::handlers::blog::slugify;
+use crate::highlight;
+use crate::toc;
+
+pub const MAX_FILES: usize = 500;
+pub const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const EXCERPT_CHARS: usize = 200;
+const WORDS_PER_MINUTE: usize = 200;
+
+/// One Markdown file from an upload, or why it couldn't be read.
+#[derive(Debug)]
+pub struct ImportFile {
+    pub name: String,
+    pub text: Result<String, String>,
+}
+
+fn is_markdown(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".markdown")
+}
+
+/// Hidden files and macOS resource forks that zip tools add.
+fn is_junk(name: &str) -> bool {
+    name.starts_with("__MACOSX/") || name.rsplit('/').next().is_some_and(|base| base.starts_with('.'))
+}
+
+/// Markdown files in an upload: every `.md` in a zip (sorted by path), or the
+/// upload itself when it's a single Markdown file.
+pub fn read_upload(file_name: &str, bytes: Vec<u8>) -> Result<Vec<ImportFile>, String> {
+    if !bytes.starts_with(b"PK\x03\x04") {
+        if !is_markdown(file_name) {
+            return Err("Upload a .zip of Markdown files or a single .md file".to_string());
+        }
+        let text = String::from_utf8(bytes).map_err(|_| "File is not valid UTF-8".to_string());
+        return Ok(vec![ImportFile { name: file_name.to_string(), text }]);
+    }
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Invalid zip: {}", e))?;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("Invalid zip: {}", e))?;
+        let name = entry.name().to_string();
+        if entry.is_dir() || is_junk(&name) || !is_markdown(&name) {
+            continue;
+        }
+        if files.len() == MAX_FILES {
+            return Err(format!("Too many Markdown files (max {})", MAX_FILES));
+        }
+
+        let text = if entry.size() > MAX_FILE_BYTES {
+            Err(format!("File too large (max {} KB)", MAX_FILE_BYTES / 1024))
+        } else {
+            let mut text = String::new();
+            entry
+                .by_ref()
+                .take(MAX_FILE_BYTES)
+                .read_to_string(&mut text)
+                .map(|_| text)
+                .map_err(|_| "File is not valid UTF-8".to_string())
+        };
+        files.push(ImportFile { name, text });
+    }
+
+    if files.is_empty() {
+        return Err("The zip contains no Markdown files".to_string());
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// CommonMark plus tables, strikethrough and footnotes. Fenced code keeps its
+/// `language-x` class for `highlight::highlight_html`.
+pub fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES;
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new_ext(markdown, options));
+    out
+}
+
+/// Plain text of the first paragraph, cut at a word boundary.
+pub fn excerpt_from_html(html: &str) -> String {
+    let Some(start) = html.find("<p>") else { return String::new() };
+    let end = html[start..].find("</p>").map(|i| start + i).unwrap_or(html.len());
+    let text = toc::decode_entities(&toc::strip_tags(&html[start + 3..end]));
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.chars().count() <= EXCERPT_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(EXCERPT_CHARS).collect();
+    let cut = cut.rsplit_once(' ').map(|(head, _)| head).unwrap_or(&cut);
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+/// Minutes to read at 200 words a minute, at least 1.
+pub fn reading_time(markdown: &str) -> i32 {
+    let words = markdown.split_whitespace().count();
+    words.div_ceil(WORDS_PER_MINUTE).max(1) as i32
+}
+
+/// Post fields from one Markdown file.
+#[derive(Debug, Clone)]
+pub struct ImportDoc {
+    pub slug: String,
+    pub title: String,
+    pub excerpt: String,
+    pub content: String,
+    pub content_markdown: String,
+    pub featured_image: Option<String>,
+    /// Author names or slugs, byline order
+    pub authors: Vec<String>,
+    pub published: bool,
+    pub published_at: Option<NaiveDateTime>,
+    pub reading_time: i32,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub meta_keywords: Option<Vec<String>>,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Parse a Markdown document with front matter into post fields.
+pub fn build_doc(text: &str) -> Result<ImportDoc, String> {
+    let (fm, body): (FrontMatter, String) = frontmatter::parse(text)?;
+    let is_published = fm.is_published();
+
+    let title = non_empty(fm.title).ok_or("Front matter needs a `title`")?;
+    let slug = slugify(&non_empty(fm.slug).unwrap_or_else(|| title.clone()));
+    if slug.is_empty() {
+        return Err("Could not derive a slug from the title".to_string());
+    }
+
+    let published_at = match non_empty(fm.date) {
+        Some(date) => Some(frontmatter::parse_date(&date).ok_or_else(|| format!("Unrecognised date: {}", date))?),
+        None => None,
+    };
+
+    let content = highlight::highlight_html(&render_markdown(&body));
+    let excerpt = non_empty(fm.excerpt).unwrap_or_else(|| excerpt_from_html(&content));
+    let clean = |values: Vec<String>| -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for value in values.into_iter().map(|v| v.trim().to_string()).filter(|v| !v.is_empty()) {
+            if !out.iter().any(|seen| slugify(seen) == slugify(&value)) {
+                out.push(value);
+            }
+        }
+        out
+    };
+    let meta_keywords = clean(fm.meta_keywords);
+    let authors = match clean(fm.authors) {
+        authors if authors.is_empty() => non_empty(fm.author).into_iter().collect(),
+        authors => authors,
+    };
+
+    Ok(ImportDoc {
+        slug,
+        title,
+        excerpt,
+        reading_time: fm.reading_time.unwrap_or_else(|| reading_time(&body)),
+        content,
+        content_markdown: body,
+        featured_image: non_empty(fm.featured_image),
+        authors,
+        published: is_published,
+        published_at,
+        meta_title: non_empty(fm.meta_title),
+        meta_description: non_empty(fm.meta_description),
+        meta_keywords: (!meta_keywords.is_empty()).then_some(meta_keywords),
+        categories: clean(fm.categories),
+        tags: clean(fm.tags),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn zip_of(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, text) in files {
+            writer.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(text.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_build_doc() {
+        let doc = build_doc(
+            "---\ntitle: Hello Rust\ndate: 2024-01-05\ntags: [Rust, rust, axum]\ncategories: [Tutorials]\n---\n\nFirst *paragraph* here.\n\n```rust\nfn main() {}\n```\n",
+        )
+        .unwrap();
+
+        assert_eq!(doc.slug, "hello-rust");
+        assert_eq!(doc.excerpt, "First paragraph here.");
+        assert_eq!(doc.tags, ["Rust", "axum"]);
+        assert_eq!(doc.categories, ["Tutorials"]);
+        assert!(doc.published);
+        assert_eq!(doc.published_at.unwrap().to_string(), "2024-01-05 00:00:00");
+        assert!(doc.content.contains("<code class=\"language-rust\">"));
+        assert!(doc.content.contains("<span class=\"hl-keyword\">fn</span>"));
+        assert_eq!(doc.reading_time, 1);
+        assert!(doc.meta_keywords.is_none());
+        assert!(doc.authors.is_empty());
+    }
+
+    #[test]
+    fn test_build_doc_authors() {
+        let byline = build_doc("---\ntitle: T\nauthor: Jane Doe\n---\n").unwrap();
+        assert_eq!(byline.authors, ["Jane Doe"]);
+
+        let co = build_doc("---\ntitle: T\nauthor: Jane Doe, Pasu\nauthors: [jane-doe, pasu, Pasu]\n---\n").unwrap();
+        assert_eq!(co.authors, ["jane-doe", "pasu"]);
+    }
+
+    #[test]
+    fn test_build_doc_errors() {
+        assert!(build_doc("---\nslug: x\n---\nBody").unwrap_err().contains("title"));
+        assert!(build_doc("---\ntitle: x\ndate: yesterday\n---\nBody").unwrap_err().contains("Unrecognised date"));
+    }
+
+    #[test]
+    fn test_excerpt_from_html_truncates() {
+        let long = format!("<h1>T</h1><p>{}</p>", "word ".repeat(100));
+        let excerpt = excerpt_from_html(&long);
+        assert!(excerpt.ends_with("word…"));
+        assert!(excerpt.chars().count() <= EXCERPT_CHARS + 1);
+        assert_eq!(excerpt_from_html("<p>A &amp; B</p>"), "A & B");
+    }
+
+    #[test]
+    fn test_read_upload_zip() {
+        let bytes = zip_of(&[
+            ("posts/b.md", "---\ntitle: B\n---\n"),
+            ("posts/a.markdown", "---\ntitle: A\n---\n"),
+            ("__MACOSX/posts/._a.md", "junk"),
+            ("posts/image.png", "png"),
+        ]);
+        let files = read_upload("bundle.zip", bytes).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["posts/a.markdown", "posts/b.md"]);
+    }
+
+    #[test]
+    fn test_read_upload_single_file() {
+        let files = read_upload("post.md", b"---\ntitle: A\n---\n".to_vec()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(read_upload("notes.txt", b"hello".to_vec()).is_err());
+        assert!(read_upload("empty.zip", zip_of(&[("a.txt", "x")])).unwrap_err().contains("no Markdown"));
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use reqwest::{redirect, Method, StatusCode};
+use sqlx::{Pool, Postgres};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::config::LinkCheckConfig;
+use crate::import;
+use crate::toc;
+
+const USER_AGENT: &str = concat!("pasu-link-checker/", env!("CARGO_PKG_VERSION"));
+
+/// Set while a run is in progress so the timer and a manual trigger don't overlap.
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Absolute http(s) `href`s of the anchors in `html`, in order, without duplicates.
+pub fn extract_links(html: &str) -> Vec<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut links = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pos = 0;
+
+    while let Some(start) = lower[pos..].find("<a").map(|i| pos + i) {
+        let end = lower[start..].find('>').map(|i| start + i).unwrap_or(lower.len());
+        pos = end;
+        if !lower[start + 2..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(href) = attribute(&html[start..end], &lower[start..end], "href") else { continue };
+        let href = toc::decode_entities(href.trim());
+        let is_http = href.starts_with("http://") || href.starts_with("https://");
+        if is_http && seen.insert(href.clone()) {
+            links.push(href);
+        }
+    }
+    links
+}
+
+/// Quoted (or bare) value of `name` inside one tag.
+fn attribute<'a>(tag: &'a str, lower: &str, name: &str) -> Option<&'a str> {
+    let mut from = 0;
+    while let Some(i) = lower[from..].find(name).map(|i| from + i) {
+        from = i + name.len();
+        let preceded = lower[..i].ends_with(|c: char| c.is_ascii_whitespace());
+        let rest = lower[from..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest[1..].trim_start().len();
+        let value = &tag[value_start..];
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split(|c: char| c.is_ascii_whitespace()).next().unwrap_or_default(),
+        });
+    }
+    None
+}
+
+/// Outcome of checking one URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkStatus {
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    /// Where redirects ended, when different from the checked URL
+    pub final_url: Option<String>,
+    pub broken: bool,
+}
+
+/// Client errors and server errors are broken; 429 only means we were too quick.
+fn is_broken(status: StatusCode) -> bool {
+    (status.is_client_error() || status.is_server_error()) && status != StatusCode::TOO_MANY_REQUESTS
+}
+
+fn describe(error: &reqwest::Error) -> String {
+    if error.is_timeout() {
+        "Timed out".to_string()
+    } else if error.is_redirect() {
+        "Too many redirects".to_string()
+    } else if error.is_connect() {
+        "Connection failed".to_string()
+    } else {
+        error.to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct LinkChecker {
+    client: reqwest::Client,
+    concurrency: usize,
+}
+
+impl LinkChecker {
+    pub fn new(config: &LinkCheckConfig) -> reqwest::Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .redirect(redirect::Policy::limited(config.max_redirects))
+            .build()?;
+        Ok(Self { client, concurrency: config.concurrency.max(1) })
+    }
+
+    /// HEAD the URL, falling back to GET when HEAD fails or is refused; plenty of
+    /// servers answer HEAD with 403/404/405 for pages that load fine.
+    pub async fn check(&self, url: &str) -> LinkStatus {
+        let response = match self.client.request(Method::HEAD, url).send().await {
+            Ok(response) if response.status().is_success() => Ok(response),
+            _ => self.client.get(url).send().await,
+        };
+
+        match response {
+            Ok(response) => {
+                let status = response.status();
+                let final_url = Some(response.url().to_string()).filter(|u| u != url);
+                LinkStatus { status_code: Some(status.as_u16()), error: None, final_url, broken: is_broken(status) }
+            }
+            Err(error) => LinkStatus {
+                status_code: error.status().map(|s| s.as_u16()),
+                error: Some(describe(&error)),
+                final_url: None,
+                broken: true,
+            },
+        }
+    }
+
+    /// Check every URL, at most `concurrency` at a time.
+    pub async fn check_all(&self, urls: Vec<String>) -> HashMap<String, LinkStatus> {
+        let permits = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+
+        for url in urls {
+            let checker = self.clone();
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let status = checker.check(&url).await;
+                (url, status)
+            });
+        }
+
+        let mut results = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((url, status)) => {
+                    results.insert(url, status);
+                }
+                Err(e) => tracing::error!("Link check task failed: {:?}", e),
+            }
+        }
+        results
+    }
+}
+
+/// One place a link appears.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkTarget {
+    /// `post`, `project` or `social`
+    pub entity: &'static str,
+    pub entity_id: i32,
+    pub url: String,
+}
+
+fn is_http(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Every outbound link in post bodies, project links and case studies, and social links.
+async fn collect_targets(pool: &Pool<Postgres>) -> Result<Vec<LinkTarget>, sqlx::Error> {
+    let mut targets = Vec::new();
+
+    let posts = sqlx::query_as::<_, (i32, String)>("SELECT id, content FROM blog_posts ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    for (id, content) in posts {
+        targets.extend(extract_links(&content).into_iter().map(|url| LinkTarget { entity: "post", entity_id: id, url }));
+    }
+
+    let case_studies = sqlx::query_as::<_, (i32, String)>("SELECT id, case_study FROM projects WHERE case_study IS NOT NULL ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    for (id, markdown) in case_studies {
+        let html = import::render_markdown(&markdown);
+        targets.extend(extract_links(&html).into_iter().map(|url| LinkTarget { entity: "project", entity_id: id, url }));
+    }
+
+    let projects = sqlx::query_as::<_, (i32, String)>("SELECT id, link FROM projects ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    let socials = sqlx::query_as::<_, (i32, String)>("SELECT id, url FROM social_links ORDER BY id")
+        .fetch_all(pool)
+        .await?;
+    for (entity, rows) in [("project", projects), ("social", socials)] {
+        for (id, url) in rows {
+            let url = url.trim().to_string();
+            if is_http(&url) {
+                targets.push(LinkTarget { entity, entity_id: id, url });
+            }
+        }
+    }
+
+    // A project's link may also appear in its case study; store each place once.
+    let mut seen = HashSet::new();
+    targets.retain(|t| seen.insert((t.entity, t.entity_id, t.url.clone())));
+    Ok(targets)
+}
+
+/// Totals from one run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct LinkCheckSummary {
+    /// Distinct URLs requested
+    pub checked: usize,
+    /// Places a broken link appears
+    pub broken: usize,
+}
+
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::Acquire)
+}
+
+/// Check every link once and replace the stored results. Returns `None` if a run
+/// was already in progress.
+pub async fn check_links(pool: &Pool<Postgres>, checker: &LinkChecker) -> Result<Option<LinkCheckSummary>, sqlx::Error> {
+    if RUNNING.swap(true, Ordering::AcqRel) {
+        return Ok(None);
+    }
+    let result = run(pool, checker).await;
+    RUNNING.store(false, Ordering::Release);
+    result.map(Some)
+}
+
+async fn run(pool: &Pool<Postgres>, checker: &LinkChecker) -> Result<LinkCheckSummary, sqlx::Error> {
+    let started: NaiveDateTime = chrono::Utc::now().naive_utc();
+    let targets = collect_targets(pool).await?;
+
+    let mut urls: Vec<String> = targets.iter().map(|t| t.url.clone()).collect();
+    urls.sort();
+    urls.dedup();
+    let checked = urls.len();
+    let results = checker.check_all(urls).await;
+
+    let mut entities = Vec::new();
+    let mut entity_ids = Vec::new();
+    let mut target_urls = Vec::new();
+    let mut status_codes = Vec::new();
+    let mut errors = Vec::new();
+    let mut final_urls = Vec::new();
+    let mut broken = Vec::new();
+    for target in &targets {
+        let Some(status) = results.get(&target.url) else { continue };
+        entities.push(target.entity.to_string());
+        entity_ids.push(target.entity_id);
+        target_urls.push(target.url.clone());
+        status_codes.push(status.status_code.map(i32::from));
+        errors.push(status.error.clone());
+        final_urls.push(status.final_url.clone());
+        broken.push(status.broken);
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+INSERT INTO link_checks (entity, entity_id, url, status_code, error, final_url, broken, checked_at)
+SELECT u.entity, u.entity_id, u.url, u.status_code, u.error, u.final_url, u.broken, $8
+FROM UNNEST($1::text[], $2::int[], $3::text[], $4::int[], $5::text[], $6::text[], $7::bool[])
+    AS u(entity, entity_id, url, status_code, error, final_url, broken)
+ON CONFLICT (entity, entity_id, url) DO UPDATE SET
+    status_code = EXCLUDED.status_code,
+    error = EXCLUDED.error,
+    final_url = EXCLUDED.final_url,
+    broken = EXCLUDED.broken,
+    checked_at = EXCLUDED.checked_at
+"#
+    )
+    .bind(&entities)
+    .bind(&entity_ids)
+    .bind(&target_urls)
+    .bind(&status_codes)
+    .bind(&errors)
+    .bind(&final_urls)
+    .bind(&broken)
+    .bind(started)
+    .execute(&mut *tx)
+    .await?;
+
+    // Links that weren't seen this run have been edited away.
+    sqlx::query("DELETE FROM link_checks WHERE checked_at < $1")
+        .bind(started)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(LinkCheckSummary { checked, broken: broken.iter().filter(|b| **b).count() })
+}
+
+/// Background task: check all links every `every`.
+pub async fn run_checker(pool: Pool<Postgres>, checker: LinkChecker, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+
+        match check_links(&pool, &checker).await {
+            Ok(Some(summary)) => {
+                tracing::info!("Checked {} links, {} broken", summary.checked, summary.broken);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!("Failed to check links: {:?}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        http::{header, StatusCode},
+        response::IntoResponse,
+        routing::{get, head},
+        Router,
+    };
+
+    #[test]
+    fn test_extract_links() {
+        let html = r#"<p><a href="https://example.com/a?x=1&amp;y=2">A</a>
+            <A class="x" HREF='http://example.com/b'>B</A>
+            <a href="/relative">R</a> <a href="mailto:me@example.com">M</a>
+            <abbr href="https://example.com/not-a-link">X</abbr>
+            <a href="https://example.com/a?x=1&amp;y=2">again</a>
+            <a data-href="https://example.com/data" href=https://example.com/bare>C</a></p>"#;
+
+        assert_eq!(
+            extract_links(html),
+            ["https://example.com/a?x=1&y=2", "http://example.com/b", "https://example.com/bare"]
+        );
+        assert!(extract_links("<p>No links</p>").is_empty());
+    }
+
+    #[test]
+    fn test_is_broken() {
+        assert!(!is_broken(StatusCode::OK));
+        assert!(!is_broken(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_broken(StatusCode::NOT_FOUND));
+        assert!(is_broken(StatusCode::BAD_GATEWAY));
+    }
+
+    /// Local stand-in for the outside world.
+    async fn stub_server() -> String {
+        let app = Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route("/missing", get(|| async { StatusCode::NOT_FOUND }))
+            .route("/moved", get(|| async { (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, "/ok")]).into_response() }))
+            .route("/loop", get(|| async { (StatusCode::FOUND, [(header::LOCATION, "/loop")]).into_response() }))
+            .route("/slow", get(|| async {
+                tokio::time::sleep(Duration::from_secs(3)).await;
+                "late"
+            }))
+            .route("/no-head", head(|| async { StatusCode::METHOD_NOT_ALLOWED }).get(|| async { "ok" }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_check_all_against_stub() {
+        let base = stub_server().await;
+        let config = LinkCheckConfig { interval_secs: 0, concurrency: 2, timeout_secs: 1, max_redirects: 3 };
+        let checker = LinkChecker::new(&config).unwrap();
+
+        // Nothing listens on port 9 of localhost.
+        let urls = ["/ok", "/missing", "/moved", "/loop", "/slow", "/no-head"]
+            .iter()
+            .map(|path| format!("{}{}", base, path))
+            .chain(["http://127.0.0.1:9/".to_string()])
+            .collect();
+        let results = checker.check_all(urls).await;
+        let result = |path: &str| results[&format!("{}{}", base, path)].clone();
+
+        assert_eq!(result("/ok"), LinkStatus { status_code: Some(200), error: None, final_url: None, broken: false });
+        assert_eq!(result("/missing").status_code, Some(404));
+        assert!(result("/missing").broken);
+
+        let moved = result("/moved");
+        assert!(!moved.broken);
+        assert_eq!(moved.final_url, Some(format!("{}/ok", base)));
+
+        assert_eq!(result("/loop").error.as_deref(), Some("Too many redirects"));
+        assert_eq!(result("/slow").error.as_deref(), Some("Timed out"));
+        assert!(!result("/no-head").broken);
+
+        let refused = &results["http://127.0.0.1:9/"];
+        assert!(refused.broken);
+        assert_eq!(refused.error.as_deref(), Some("Connection failed"));
+    }
+}
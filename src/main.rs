@@ -1,15 +1,31 @@
 mod cache;
+mod comments;
+mod config;
 mod db;
 pub mod error;
+mod export;
+mod feeds;
+mod frontmatter;
 mod handlers;
+mod highlight;
+mod i18n;
+mod import;
+mod links;
 mod middleware;
 pub mod models;
+mod ordering;
+mod preview;
+mod scheduler;
+mod sitemap;
 mod state;
+mod toc;
+mod views;
 
 use state::AppState;
 
 use axum::{
-    routing::{get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post, put},
     Router,
 };
 use tower_http::cors::{Any, CorsLayer};
@@ -26,6 +42,7 @@ use utoipa_swagger_ui::SwaggerUi;
         handlers::skills::get_skills,
         handlers::experience::get_experience,
         handlers::projects::get_projects,
+        handlers::projects::get_project,
         handlers::contact::get_contact_info,
         handlers::contact::get_social_links,
         handlers::contact::submit_contact_message,
@@ -33,6 +50,32 @@ use utoipa_swagger_ui::SwaggerUi;
         handlers::blog::get_post_by_slug,
         handlers::blog::get_categories,
         handlers::blog::get_tags,
+        handlers::blog::get_category_posts,
+        handlers::blog::get_tag_posts,
+        handlers::blog::get_highlight_css,
+        handlers::blog::record_view,
+        handlers::blog::get_popular_posts,
+        handlers::blog::get_related_posts,
+        handlers::archive::get_archive,
+        handlers::archive::get_archive_month,
+        handlers::comments::get_comments,
+        handlers::comments::submit_comment,
+        handlers::series::get_series_list,
+        handlers::authors::get_authors,
+        handlers::authors::get_author,
+        handlers::series::get_series,
+        handlers::translations::get_translations,
+        handlers::translations::put_translation,
+        handlers::translations::delete_translation,
+        handlers::feeds::rss_feed,
+        handlers::feeds::atom_feed,
+        handlers::feeds::json_feed,
+        handlers::feeds::category_rss_feed,
+        handlers::feeds::category_atom_feed,
+        handlers::feeds::tag_rss_feed,
+        handlers::feeds::tag_atom_feed,
+        handlers::sitemap::sitemap,
+        handlers::sitemap::robots,
         handlers::admin::login,
         handlers::upload::upload_image,
         handlers::health::health,
@@ -44,12 +87,33 @@ use utoipa_swagger_ui::SwaggerUi;
             models::Skill,
             models::ExperienceTimeline,
             models::Project,
+            models::ProjectSummary,
             models::ContactInfo,
             models::SocialLink,
             models::ContactMessage,
             models::BlogCategory,
             models::BlogTag,
             models::BlogPost,
+            models::PopularPost,
+            models::RelatedPost,
+            models::BlogSeries,
+            models::BlogAuthor,
+            models::SeriesPost,
+            models::CommentNode,
+            models::TocEntry,
+            models::ArchiveYear,
+            models::ArchiveMonth,
+            models::Translation,
+            handlers::comments::CommentPayload,
+            handlers::comments::CommentSubmitted,
+            handlers::series::SeriesDetail,
+            handlers::authors::AuthorPosts,
+            handlers::translations::TranslationPayload,
+            handlers::blog::ViewResponse,
+            handlers::blog::SlugRedirect,
+            handlers::blog::CategoryPosts,
+            handlers::blog::TagPosts,
+            handlers::archive::ArchivePosts,
             handlers::contact::ContactMessagePayload,
             handlers::admin::LoginPayload,
             handlers::admin::LoginResponse,
@@ -78,6 +142,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     let pool = db::init_pool().await?;
+    db::run_migrations(&pool).await?;
+    match db::highlight_stored_posts(&pool).await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Highlighted code blocks in {} stored posts and translations", n),
+        Err(e) => tracing::error!("Failed to highlight stored posts: {:?}", e),
+    }
 
     let state = AppState {
         pool: pool.clone(),
@@ -88,8 +158,54 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         socials_cache: crate::cache::AppCache::new(100, 300),
         categories_cache: crate::cache::AppCache::new(100, 300),
         tags_cache: crate::cache::AppCache::new(100, 300),
+        posts_cache: crate::cache::AppCache::new(100, 300),
+        feeds_cache: crate::cache::AppCache::new(100, 300),
+        related_cache: crate::cache::AppCache::new(500, 300),
+        comments_cache: crate::cache::AppCache::new(500, 300),
+        archive_cache: crate::cache::AppCache::new(10, 300),
+        translations_cache: crate::cache::AppCache::new(10, 300),
+        views: crate::views::ViewCounter::new(crate::views::salt_from_env()),
+        site: crate::config::SiteConfig::from_env(),
+        link_check: crate::config::LinkCheckConfig::from_env(),
     };
 
+    // A zero period would panic inside the flusher, so it falls back to the default.
+    let view_flush_secs = std::env::var("VIEW_FLUSH_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .unwrap_or(30);
+    tokio::spawn(views::run_flusher(
+        state.views.clone(),
+        pool.clone(),
+        std::time::Duration::from_secs(view_flush_secs),
+    ));
+    let views = state.views.clone();
+
+    // As above: scheduled posts must keep being released, so 0 means the default.
+    let scheduler_secs = std::env::var("SCHEDULER_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .unwrap_or(60);
+    tokio::spawn(scheduler::run_publisher(
+        state.clone(),
+        std::time::Duration::from_secs(scheduler_secs),
+    ));
+
+    if state.link_check.interval_secs > 0 {
+        match links::LinkChecker::new(&state.link_check) {
+            Ok(checker) => {
+                tokio::spawn(links::run_checker(
+                    pool.clone(),
+                    checker,
+                    std::time::Duration::from_secs(state.link_check.interval_secs),
+                ));
+            }
+            Err(e) => tracing::error!("Link checker disabled: {:?}", e),
+        }
+    }
+
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
@@ -106,31 +222,84 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/about", get(handlers::about::get_about).post(handlers::about::update_about))
         // Public + Admin: Skills
         .route("/api/skills", get(handlers::skills::get_skills).post(handlers::skills::create_skill))
+        .route("/api/skills/reorder", put(handlers::skills::reorder_skills))
         .route("/api/skills/:id", put(handlers::skills::update_skill).delete(handlers::skills::delete_skill))
         // Public + Admin: Experience
         .route("/api/experience", get(handlers::experience::get_experience))
         .route("/api/experience/timeline", post(handlers::experience::create_timeline))
+        .route("/api/experience/timeline/reorder", put(handlers::experience::reorder_timeline))
         .route("/api/experience/timeline/:id", put(handlers::experience::update_timeline).delete(handlers::experience::delete_timeline))
         // Public + Admin: Projects
         .route("/api/projects", get(handlers::projects::get_projects).post(handlers::projects::create_project))
+        .route("/api/projects/reorder", put(handlers::projects::reorder_projects))
+        .route("/api/projects/:slug", get(handlers::projects::get_project))
         .route("/api/experience/projects", get(handlers::projects::get_projects).post(handlers::projects::create_project))
+        .route("/api/experience/projects/reorder", put(handlers::projects::reorder_projects))
         .route("/api/experience/projects/:id", put(handlers::projects::update_project).delete(handlers::projects::delete_project))
         // Public + Admin: Contact
         .route("/api/contact", get(handlers::contact::get_contact_info).post(handlers::contact::submit_contact_message))
         .route("/api/contact/info", post(handlers::contact::update_contact_info))
         .route("/api/contact/socials", get(handlers::contact::get_social_links).post(handlers::contact::create_social))
+        .route("/api/contact/socials/reorder", put(handlers::contact::reorder_socials))
         .route("/api/contact/socials/:id", put(handlers::contact::update_social).delete(handlers::contact::delete_social))
         .route("/api/contact/messages", get(handlers::contact::get_messages).delete(handlers::contact::delete_message))
         // Public + Admin: Blog
         .route("/api/blog/posts", get(handlers::blog::get_posts).post(handlers::blog::create_post))
+        .route("/api/blog/posts/popular", get(handlers::blog::get_popular_posts))
         .route("/api/blog/posts/:slug", get(handlers::blog::get_post_by_slug))
+        .route("/api/blog/posts/:slug/view", post(handlers::blog::record_view))
+        .route("/api/blog/posts/:slug/related", get(handlers::blog::get_related_posts))
+        .route("/api/blog/posts/:slug/comments", get(handlers::comments::get_comments).post(handlers::comments::submit_comment))
+        .route("/api/blog/admin/export", get(handlers::export::export_posts))
+        .route("/api/blog/admin/import", post(handlers::import::import_posts).layer(DefaultBodyLimit::max(handlers::import::MAX_UPLOAD_BYTES)))
+        .route("/api/blog/admin/scheduled", get(handlers::blog::get_scheduled_posts))
         .route("/api/blog/admin/posts/:id", get(handlers::blog::get_post_by_id).put(handlers::blog::update_post).delete(handlers::blog::delete_post))
+        .route("/api/blog/admin/posts/:id/preview", post(handlers::blog::create_preview_link).delete(handlers::blog::revoke_preview_links))
+        .route("/api/blog/admin/posts/:id/comments", put(handlers::comments::set_comments_closed))
+        .route("/api/blog/admin/comments", get(handlers::comments::get_admin_comments))
+        .route("/api/blog/admin/comments/:id", delete(handlers::comments::delete_comment))
+        .route("/api/blog/admin/comments/:id/approve", post(handlers::comments::approve_comment))
+        .route("/api/blog/admin/comments/:id/reject", post(handlers::comments::reject_comment))
+        .route("/api/blog/admin/comments/:id/spam", post(handlers::comments::mark_comment_spam))
+        .route("/api/blog/admin/posts/:id/revisions", get(handlers::revisions::get_revisions))
+        .route("/api/blog/admin/posts/:id/revisions/diff", get(handlers::revisions::get_revision_diff))
+        .route("/api/blog/admin/posts/:id/revisions/:rev/restore", post(handlers::revisions::restore_revision))
+        .route("/api/blog/archive", get(handlers::archive::get_archive))
+        .route("/api/blog/archive/:year/:month", get(handlers::archive::get_archive_month))
+        .route("/api/blog/highlight.css", get(handlers::blog::get_highlight_css))
+        .route("/api/blog/authors", get(handlers::authors::get_authors))
+        .route("/api/blog/authors/:slug", get(handlers::authors::get_author))
+        .route("/api/blog/admin/authors", post(handlers::authors::create_author))
+        .route("/api/blog/admin/authors/:id", put(handlers::authors::update_author).delete(handlers::authors::delete_author))
+        .route("/api/blog/series", get(handlers::series::get_series_list))
+        .route("/api/blog/series/:slug", get(handlers::series::get_series))
+        .route("/api/blog/admin/series", post(handlers::series::create_series))
+        .route("/api/blog/admin/series/:id", put(handlers::series::update_series).delete(handlers::series::delete_series))
+        .route("/api/blog/admin/series/:id/posts", put(handlers::series::set_series_posts))
+        .route("/api/blog/categories/:slug/feed.xml", get(handlers::feeds::category_rss_feed))
+        .route("/api/blog/categories/:slug/atom.xml", get(handlers::feeds::category_atom_feed))
         .route("/api/blog/categories", get(handlers::blog::get_categories).post(handlers::blog::create_category))
-        .route("/api/blog/categories/:id", put(handlers::blog::update_category).delete(handlers::blog::delete_category))
+        .route("/api/blog/categories/:id", get(handlers::blog::get_category_posts).put(handlers::blog::update_category).delete(handlers::blog::delete_category))
+        .route("/api/blog/tags/:slug/feed.xml", get(handlers::feeds::tag_rss_feed))
+        .route("/api/blog/tags/:slug/atom.xml", get(handlers::feeds::tag_atom_feed))
         .route("/api/blog/tags", get(handlers::blog::get_tags).post(handlers::blog::create_tag))
-        .route("/api/blog/tags/:id", put(handlers::blog::update_tag).delete(handlers::blog::delete_tag))
+        .route("/api/blog/tags/:id", get(handlers::blog::get_tag_posts).put(handlers::blog::update_tag).delete(handlers::blog::delete_tag))
+        .route("/api/blog/admin/tags/:id/merge", post(handlers::tags::merge_tag))
+        .route("/api/blog/admin/tags/:id/posts", post(handlers::tags::bulk_tag_posts))
+        // Admin: Translations
+        .route("/api/translations/:entity/:id", get(handlers::translations::get_translations))
+        .route("/api/translations/:entity/:id/:locale", put(handlers::translations::put_translation).delete(handlers::translations::delete_translation))
+        // Public: Feeds
+        .route("/feed.xml", get(handlers::feeds::rss_feed))
+        .route("/atom.xml", get(handlers::feeds::atom_feed))
+        .route("/feed.json", get(handlers::feeds::json_feed))
+        .route("/sitemap.xml", get(handlers::sitemap::sitemap))
+        .route("/robots.txt", get(handlers::sitemap::robots))
         // Admin: Auth
         .route("/api/admin/login", post(handlers::admin::login))
+        // Admin: Link checks
+        .route("/api/admin/links", get(handlers::links::get_link_report))
+        .route("/api/admin/links/check", post(handlers::links::start_link_check))
         // Upload
         .route("/api/upload", post(handlers::upload::upload_image))
         // Health Checks
@@ -149,7 +318,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Listening on {}", addr);
     
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // Don't drop views still buffered in memory.
+    tracing::info!("Flushing {} pending blog views", views.pending_len());
+    views.flush(&pool).await?;
 
     Ok(())
 }
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
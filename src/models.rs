@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::ToSchema;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 
 // Formats for uuid, etc. Although schema uses SERIAL (int) for IDs.
 
@@ -18,6 +18,14 @@ pub struct About {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Locale the text fields are in; set on public responses
+    #[serde(default)]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    /// Locales this item has text for, default first
+    #[serde(default)]
+    #[sqlx(default)]
+    pub available_locales: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -34,6 +42,14 @@ pub struct Skill {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Locale the text fields are in; set on public responses
+    #[serde(default)]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    /// Locales this item has text for, default first
+    #[serde(default)]
+    #[sqlx(default)]
+    pub available_locales: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -55,17 +71,38 @@ pub struct ExperienceTimeline {
     #[serde(default)]
     #[sqlx(default)]
     pub details: Option<serde_json::Value>,
+    /// Locale the text fields are in; set on public responses
+    #[serde(default)]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    /// Locales this item has text for, default first
+    #[serde(default)]
+    #[sqlx(default)]
+    pub available_locales: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Project {
     pub id: i32,
+    pub slug: String,
     pub title: String,
     pub description: String,
     pub image: String,
     pub skills: Option<Vec<String>>,
     pub link: String,
     pub is_active: Option<bool>,
+    /// What I did on the project, e.g. "Lead backend developer"
+    pub role: Option<String>,
+    pub team_size: Option<i32>,
+    pub start_date: Option<NaiveDate>,
+    /// `None` while the project is ongoing
+    pub end_date: Option<NaiveDate>,
+    /// Markdown case study: problem, approach, results
+    pub case_study: Option<String>,
+    /// `case_study` rendered to HTML; set on the detail response
+    #[serde(default)]
+    #[sqlx(default)]
+    pub case_study_html: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     #[serde(default)]
@@ -74,6 +111,55 @@ pub struct Project {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Locale the text fields are in; set on public responses
+    #[serde(default)]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    /// Locales this item has text for, default first
+    #[serde(default)]
+    #[sqlx(default)]
+    pub available_locales: Option<Vec<String>>,
+}
+
+/// A project as listed on `/api/projects`; the case study is on the detail endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProjectSummary {
+    pub id: i32,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub image: String,
+    pub skills: Option<Vec<String>>,
+    pub link: String,
+    pub role: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Whether there is a case study to link to
+    pub has_case_study: bool,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub available_locales: Option<Vec<String>>,
+}
+
+impl From<Project> for ProjectSummary {
+    fn from(project: Project) -> Self {
+        Self {
+            has_case_study: project.case_study.as_deref().is_some_and(|c| !c.trim().is_empty()),
+            id: project.id,
+            slug: project.slug,
+            title: project.title,
+            description: project.description,
+            image: project.image,
+            skills: project.skills,
+            link: project.link,
+            role: project.role,
+            start_date: project.start_date,
+            end_date: project.end_date,
+            locale: project.locale,
+            available_locales: project.available_locales,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
@@ -133,6 +219,10 @@ pub struct BlogCategory {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Published posts filed under it; set on the public listings
+    #[serde(default)]
+    #[sqlx(default)]
+    pub post_count: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -148,9 +238,13 @@ pub struct BlogTag {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Published posts filed under it; set on the public listings
+    #[serde(default)]
+    #[sqlx(default)]
+    pub post_count: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct BlogPost {
     pub id: i32,
     pub uuid: Option<String>,
@@ -176,4 +270,192 @@ pub struct BlogPost {
     #[serde(default)]
     #[sqlx(default)]
     pub tags: Option<serde_json::Value>,
+    /// Authors in byline order (`id`, `name`, `slug`, `avatar`)
+    #[serde(default)]
+    #[sqlx(default)]
+    pub authors: Option<serde_json::Value>,
+    /// Series membership with previous/next parts; only on the single-post response
+    #[serde(default)]
+    #[sqlx(default)]
+    pub series: Option<serde_json::Value>,
+    /// Nearest older published post (`id`, `title`, `slug`, `published_at`); only on the single-post response
+    #[serde(default)]
+    #[sqlx(default)]
+    pub previous_post: Option<serde_json::Value>,
+    /// Nearest newer published post; only on the single-post response
+    #[serde(default)]
+    #[sqlx(default)]
+    pub next_post: Option<serde_json::Value>,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub comments_closed: Option<bool>,
+    /// Headings of the post body; only on the single-post response
+    #[serde(default)]
+    #[sqlx(skip)]
+    pub toc: Option<Vec<TocEntry>>,
+    /// Locale the text fields are in; set on public responses
+    #[serde(default)]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    /// Locales this item has text for, default first
+    #[serde(default)]
+    #[sqlx(default)]
+    pub available_locales: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TocEntry {
+    /// Heading level, 1-6
+    pub level: u8,
+    pub text: String,
+    /// `id` of the heading element in `content`
+    pub anchor: String,
+}
+
+/// A comment as moderators see it, including the commenter's email and raw body.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BlogComment {
+    pub id: i32,
+    pub post_id: i32,
+    pub parent_id: Option<i32>,
+    pub author_name: String,
+    pub author_email: String,
+    pub body: String,
+    pub body_html: String,
+    /// `pending`, `approved`, `rejected` or `spam`
+    pub status: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub post_slug: Option<String>,
+}
+
+/// An approved comment with its approved replies, as shown to readers.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CommentNode {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub author_name: String,
+    pub body_html: String,
+    pub created_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    #[sqlx(skip)]
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentNode>,
+}
+
+/// Published posts in one month, by `published_at` (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ArchiveMonth {
+    pub year: i32,
+    /// 1-12
+    pub month: i32,
+    pub post_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArchiveYear {
+    pub year: i32,
+    pub post_count: i64,
+    /// Months with at least one post, newest first
+    pub months: Vec<ArchiveMonth>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BlogSeries {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    /// Published parts
+    #[serde(default)]
+    #[sqlx(default)]
+    pub post_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BlogAuthor {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    /// Site name → profile URL
+    pub social_links: Option<serde_json::Value>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    /// Published posts they wrote or co-wrote
+    #[serde(default)]
+    #[sqlx(default)]
+    pub post_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SeriesPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub featured_image: Option<String>,
+    pub published_at: Option<NaiveDateTime>,
+    /// 1-based part number
+    pub position: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PopularPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub featured_image: Option<String>,
+    pub published_at: Option<NaiveDateTime>,
+    pub view_count: Option<i32>,
+    /// Unique views inside the requested window
+    pub window_views: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RelatedPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub featured_image: Option<String>,
+    pub published_at: Option<NaiveDateTime>,
+    pub shared_tags: i64,
+    pub shared_categories: i64,
+    /// Higher is more related; tags weigh more than categories
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BlogPostRevision {
+    pub id: i32,
+    pub post_id: i32,
+    pub title: String,
+    pub excerpt: String,
+    pub content: String,
+    pub content_markdown: Option<String>,
+    pub author: Option<String>,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub meta_keywords: Option<Vec<String>>,
+    pub category_ids: Vec<i32>,
+    pub tag_ids: Vec<i32>,
+    /// Byline order
+    pub author_ids: Vec<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// One locale's overrides for a translatable item.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Translation {
+    pub locale: String,
+    /// Field name → translated text
+    pub fields: serde_json::Value,
+    pub updated_at: Option<NaiveDateTime>,
 }
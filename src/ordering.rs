@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use sqlx::{Pool, Postgres};
+
+use crate::error::AppError;
+
+/// Body of the `PUT /api/<entity>/reorder` endpoints: every id, in display order.
+#[derive(Deserialize, Debug)]
+pub struct ReorderPayload {
+    pub ids: Vec<i32>,
+}
+
+/// `ids` must list every existing row exactly once, so no row is left with a
+/// stale position.
+pub fn validate_order(existing: &[i32], ids: &[i32]) -> Result<(), String> {
+    let mut seen = HashSet::with_capacity(ids.len());
+    if let Some(id) = ids.iter().find(|id| !seen.insert(**id)) {
+        return Err(format!("Id {} is listed more than once", id));
+    }
+
+    let existing: HashSet<i32> = existing.iter().copied().collect();
+    if let Some(id) = ids.iter().find(|id| !existing.contains(id)) {
+        return Err(format!("Id {} does not exist", id));
+    }
+    let mut missing: Vec<i32> = existing.difference(&seen).copied().collect();
+    if !missing.is_empty() {
+        missing.sort_unstable();
+        let missing: Vec<String> = missing.iter().map(i32::to_string).collect();
+        return Err(format!("Missing ids: {}", missing.join(", ")));
+    }
+    Ok(())
+}
+
+/// Set `sort_order` of every row in `table` to its index in `ids`, in one
+/// transaction. `table` is one of the fixed profile tables, never user input.
+pub async fn apply_order(pool: &Pool<Postgres>, table: &'static str, ids: &[i32]) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+
+    // Row locks don't stop inserts, so lock the table against writes until commit:
+    // a concurrent create or delete can't slip between the check and the update.
+    sqlx::query(&format!("LOCK TABLE {} IN SHARE ROW EXCLUSIVE MODE", table))
+        .execute(&mut *tx)
+        .await?;
+    let existing: Vec<i32> = sqlx::query_scalar(&format!("SELECT id FROM {} ORDER BY id", table))
+        .fetch_all(&mut *tx)
+        .await?;
+    validate_order(&existing, ids).map_err(AppError::ValidationError)?;
+
+    sqlx::query(&format!(
+        "UPDATE {table} t SET sort_order = o.position - 1, updated_at = NOW() \
+         FROM UNNEST($1::int[]) WITH ORDINALITY AS o(id, position) \
+         WHERE t.id = o.id AND t.sort_order IS DISTINCT FROM (o.position - 1)::int",
+    ))
+    .bind(ids)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_order() {
+        assert_eq!(validate_order(&[1, 2, 3], &[3, 1, 2]), Ok(()));
+        assert_eq!(validate_order(&[], &[]), Ok(()));
+        assert_eq!(validate_order(&[1, 2], &[1, 2, 1]), Err("Id 1 is listed more than once".to_string()));
+        assert_eq!(validate_order(&[1, 2], &[1, 2, 9]), Err("Id 9 does not exist".to_string()));
+        assert_eq!(validate_order(&[1, 2, 3, 4], &[2]), Err("Missing ids: 1, 3, 4".to_string()));
+    }
+}
@@ -0,0 +1,104 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// What a valid preview token grants: read access to one post at one preview version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewClaims {
+    pub post_id: i32,
+    pub version: i32,
+    pub expires_at: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PreviewError {
+    Malformed,
+    BadSignature,
+    Expired,
+}
+
+fn mac(secret: &str) -> HmacSha256 {
+    HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length")
+}
+
+/// Token format: `base64url(post_id.version.expires_at).base64url(hmac)`.
+pub fn sign(claims: &PreviewClaims, secret: &str) -> String {
+    let payload = format!("{}.{}.{}", claims.post_id, claims.version, claims.expires_at);
+    let mut mac = mac(secret);
+    mac.update(payload.as_bytes());
+    let signature = mac.finalize().into_bytes();
+
+    format!("{}.{}", URL_SAFE_NO_PAD.encode(payload), URL_SAFE_NO_PAD.encode(signature))
+}
+
+/// Check the signature and expiry (`now` is a unix timestamp). Revocation is checked
+/// by the caller against the post's current `preview_version`.
+pub fn verify(token: &str, secret: &str, now: i64) -> Result<PreviewClaims, PreviewError> {
+    let (payload_b64, signature_b64) = token.split_once('.').ok_or(PreviewError::Malformed)?;
+    let payload = URL_SAFE_NO_PAD.decode(payload_b64).map_err(|_| PreviewError::Malformed)?;
+    let signature = URL_SAFE_NO_PAD.decode(signature_b64).map_err(|_| PreviewError::Malformed)?;
+
+    let mut mac = mac(secret);
+    mac.update(&payload);
+    mac.verify_slice(&signature).map_err(|_| PreviewError::BadSignature)?;
+
+    let payload = String::from_utf8(payload).map_err(|_| PreviewError::Malformed)?;
+    let parts: Vec<&str> = payload.split('.').collect();
+    if parts.len() != 3 {
+        return Err(PreviewError::Malformed);
+    }
+
+    let claims = PreviewClaims {
+        post_id: parts[0].parse().map_err(|_| PreviewError::Malformed)?,
+        version: parts[1].parse().map_err(|_| PreviewError::Malformed)?,
+        expires_at: parts[2].parse().map_err(|_| PreviewError::Malformed)?,
+    };
+
+    if claims.expires_at < now {
+        return Err(PreviewError::Expired);
+    }
+
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims() -> PreviewClaims {
+        PreviewClaims { post_id: 7, version: 2, expires_at: 1_700_000_000 }
+    }
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let token = sign(&claims(), "secret");
+        assert_eq!(verify(&token, "secret", 1_600_000_000), Ok(claims()));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_secret() {
+        let token = sign(&claims(), "secret");
+        assert_eq!(verify(&token, "other", 1_600_000_000), Err(PreviewError::BadSignature));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload() {
+        let token = sign(&claims(), "secret");
+        let (_, signature) = token.split_once('.').unwrap();
+        let forged = format!("{}.{}", URL_SAFE_NO_PAD.encode("8.2.1700000000"), signature);
+        assert_eq!(verify(&forged, "secret", 1_600_000_000), Err(PreviewError::BadSignature));
+    }
+
+    #[test]
+    fn test_verify_rejects_expired() {
+        let token = sign(&claims(), "secret");
+        assert_eq!(verify(&token, "secret", 1_700_000_001), Err(PreviewError::Expired));
+    }
+
+    #[test]
+    fn test_verify_rejects_garbage() {
+        assert_eq!(verify("not-a-token", "secret", 0), Err(PreviewError::Malformed));
+    }
+}
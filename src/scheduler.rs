@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::state::AppState;
+
+/// Release scheduled posts whose `published_at` has passed. Returns the released slugs.
+pub async fn publish_due_posts(state: &AppState) -> Result<Vec<String>, sqlx::Error> {
+    let slugs = sqlx::query_scalar::<_, String>(
+        "UPDATE blog_posts SET scheduled = false WHERE scheduled = true AND published = true AND published_at <= NOW() RETURNING slug"
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    if !slugs.is_empty() {
+        state.invalidate_posts().await;
+    }
+
+    Ok(slugs)
+}
+
+/// Background task: check for due scheduled posts every `every`.
+pub async fn run_publisher(state: AppState, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+
+        match publish_due_posts(&state).await {
+            Ok(slugs) if !slugs.is_empty() => {
+                tracing::info!("Published scheduled posts: {}", slugs.join(", "));
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to publish scheduled posts: {:?}", e),
+        }
+    }
+}
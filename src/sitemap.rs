@@ -0,0 +1,111 @@
+use chrono::NaiveDateTime;
+
+use crate::config::SiteConfig;
+use crate::feeds::escape_xml;
+
+/// One `<url>` (or `<sitemap>` in an index) entry.
+#[derive(Clone, Debug)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<NaiveDateTime>,
+    pub priority: f32,
+}
+
+/// W3C datetime as required by the sitemap protocol.
+fn w3c_date(date: NaiveDateTime) -> String {
+    date.and_utc().format("%Y-%m-%dT%H:%M:%S+00:00").to_string()
+}
+
+pub fn render_urlset(entries: &[SitemapEntry]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for entry in entries {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&entry.loc)));
+        if let Some(lastmod) = entry.lastmod {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", w3c_date(lastmod)));
+        }
+        xml.push_str(&format!("    <priority>{:.1}</priority>\n", entry.priority));
+        xml.push_str("  </url>\n");
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Index pointing at `{sitemap_url}?page=N` for each chunk of `max_urls` entries.
+pub fn render_index(sitemap_url: &str, entries: &[SitemapEntry], max_urls: usize) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+    for (i, chunk) in entries.chunks(max_urls.max(1)).enumerate() {
+        xml.push_str("  <sitemap>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&format!("{}?page={}", sitemap_url, i + 1))));
+        if let Some(lastmod) = chunk.iter().filter_map(|e| e.lastmod).max() {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", w3c_date(lastmod)));
+        }
+        xml.push_str("  </sitemap>\n");
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+pub fn render_robots(site: &SiteConfig) -> String {
+    format!(
+        "User-agent: *\nAllow: /\nDisallow: /api/admin/\nDisallow: /api/blog/admin/\n\nSitemap: {}\n",
+        site.api_path_url("/sitemap.xml")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SitemapConfig;
+    use chrono::NaiveDate;
+
+    fn entry(loc: &str, day: u32) -> SitemapEntry {
+        SitemapEntry {
+            loc: loc.to_string(),
+            lastmod: NaiveDate::from_ymd_opt(2024, 1, day).unwrap().and_hms_opt(0, 0, 0),
+            priority: 0.7,
+        }
+    }
+
+    #[test]
+    fn test_render_urlset() {
+        let xml = render_urlset(&[entry("https://pasu.app/blog/a&b", 2)]);
+        assert!(xml.contains("<loc>https://pasu.app/blog/a&amp;b</loc>"));
+        assert!(xml.contains("<lastmod>2024-01-02T00:00:00+00:00</lastmod>"));
+        assert!(xml.contains("<priority>0.7</priority>"));
+    }
+
+    #[test]
+    fn test_render_index_chunks() {
+        let entries = vec![entry("a", 1), entry("b", 5), entry("c", 3)];
+        let xml = render_index("https://api.pasu.app/sitemap.xml", &entries, 2);
+        assert!(xml.contains("<loc>https://api.pasu.app/sitemap.xml?page=1</loc>"));
+        assert!(xml.contains("<loc>https://api.pasu.app/sitemap.xml?page=2</loc>"));
+        assert!(!xml.contains("page=3"));
+        assert!(xml.contains("<lastmod>2024-01-05T00:00:00+00:00</lastmod>"));
+    }
+
+    #[test]
+    fn test_render_robots() {
+        let site = SiteConfig {
+            base_url: "https://pasu.app".to_string(),
+            api_url: "https://api.pasu.app".to_string(),
+            title: "PASU.APP".to_string(),
+            description: "Blog".to_string(),
+            language: "en".to_string(),
+            locales: vec!["en".to_string(), "th".to_string()],
+            author: "Pasu".to_string(),
+            blog_path: "/blog".to_string(),
+            feed_full_content: false,
+            feed_limit: 20,
+            sitemap: SitemapConfig::default(),
+        };
+        let robots = render_robots(&site);
+        assert!(robots.contains("Sitemap: https://api.pasu.app/sitemap.xml"));
+        assert!(robots.contains("Disallow: /api/blog/admin/"));
+    }
+}
@@ -1,6 +1,11 @@
+use std::sync::Arc;
 use sqlx::{Pool, Postgres};
 use crate::cache::AppCache;
-use crate::models::{About, Skill, ExperienceTimeline, Project, SocialLink, BlogCategory, BlogTag};
+use crate::config::{LinkCheckConfig, SiteConfig};
+use crate::feeds::CachedFeed;
+use crate::i18n::TranslationMap;
+use crate::views::ViewCounter;
+use crate::models::{About, Skill, ExperienceTimeline, Project, SocialLink, BlogCategory, BlogTag, BlogPost, RelatedPost, CommentNode, ArchiveYear};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -12,4 +17,28 @@ pub struct AppState {
     pub socials_cache: AppCache<String, Vec<SocialLink>>,
     pub categories_cache: AppCache<String, Vec<BlogCategory>>,
     pub tags_cache: AppCache<String, Vec<BlogTag>>,
+    pub posts_cache: AppCache<String, Vec<BlogPost>>,
+    pub feeds_cache: AppCache<String, CachedFeed>,
+    pub related_cache: AppCache<String, Vec<RelatedPost>>,
+    pub comments_cache: AppCache<String, Vec<CommentNode>>,
+    pub archive_cache: AppCache<String, Vec<ArchiveYear>>,
+    /// Translation overlays keyed by entity name (`posts`, `skills`, …)
+    pub translations_cache: AppCache<String, Arc<TranslationMap>>,
+    pub views: ViewCounter,
+    pub site: SiteConfig,
+    pub link_check: LinkCheckConfig,
+}
+
+impl AppState {
+    /// Drop every cache derived from the set of published posts.
+    pub async fn invalidate_posts(&self) {
+        self.posts_cache.invalidate(&String::from("posts")).await;
+        // Listings carry published-post counts.
+        self.categories_cache.invalidate_all().await;
+        self.tags_cache.invalidate_all().await;
+        self.feeds_cache.invalidate_all().await;
+        self.related_cache.invalidate_all().await;
+        self.comments_cache.invalidate_all().await;
+        self.archive_cache.invalidate(&String::from("archive")).await;
+    }
 }
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use crate::models::TocEntry;
+
+/// GitHub-style anchor: lowercase, letters and digits kept (any script), runs of
+/// spaces and dashes collapsed to one `-`, other punctuation dropped.
+pub fn anchor_id(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            out.push(c);
+        } else if (c.is_whitespace() || c == '-') && !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() { String::from("section") } else { out }
+}
+
+/// `base`, or `base-1`, `base-2`, … whichever is not used yet. Records the result.
+fn unique_anchor(base: String, used: &mut HashSet<String>) -> String {
+    let anchor = if used.contains(&base) {
+        (1..).map(|n| format!("{}-{}", base, n)).find(|a| !used.contains(a)).unwrap()
+    } else {
+        base
+    };
+    used.insert(anchor.clone());
+    anchor
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Value of an `id` attribute in the attribute part of an opening tag.
+fn existing_id(attrs: &str) -> Option<String> {
+    let lower = attrs.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find("id=") {
+        let at = from + found;
+        from = at + 3;
+        if !attrs[..at].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let value = &attrs[at + 3..];
+        let quote = value.chars().next()?;
+        return if quote == '"' || quote == '\'' {
+            value[1..].split(quote).next().map(str::to_string)
+        } else {
+            value.split(|c: char| c.is_whitespace() || c == '>').next().map(str::to_string)
+        };
+    }
+    None
+}
+
+/// Headings in rendered HTML. Each `<hN>` without an id gets one; the returned HTML
+/// is the input with those ids added.
+pub fn from_html(html: &str) -> (String, Vec<TocEntry>) {
+    let lower = html.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut out = String::with_capacity(html.len() + 64);
+    let mut entries = Vec::new();
+    let mut used = HashSet::new();
+    let mut copied = 0;
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("<h") {
+        let start = pos + found;
+        pos = start + 2;
+
+        let level = match bytes.get(start + 2) {
+            Some(b) if (b'1'..=b'6').contains(b) => b - b'0',
+            _ => continue,
+        };
+        if !matches!(bytes.get(start + 3), Some(b'>') | Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r')) {
+            continue;
+        }
+        let Some(open_end) = lower[start..].find('>').map(|i| start + i) else { break };
+        let closing = format!("</h{}", level);
+        let Some(close_start) = lower[open_end..].find(&closing).map(|i| open_end + i) else { continue };
+
+        let attrs = &html[start + 3..open_end];
+        let text = collapse_whitespace(&decode_entities(&strip_tags(&html[open_end + 1..close_start])));
+
+        let anchor = match existing_id(attrs) {
+            Some(id) => {
+                used.insert(id.clone());
+                id
+            }
+            None => {
+                let anchor = unique_anchor(anchor_id(&text), &mut used);
+                out.push_str(&html[copied..open_end]);
+                out.push_str(&format!(" id=\"{}\"", anchor));
+                copied = open_end;
+                anchor
+            }
+        };
+
+        entries.push(TocEntry { level, text, anchor });
+        pos = close_start;
+    }
+
+    out.push_str(&html[copied..]);
+    (out, entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_id() {
+        assert_eq!(anchor_id("Getting Started with Rust!"), "getting-started-with-rust");
+        assert_eq!(anchor_id("  What's  new -- in 1.80? "), "whats-new-in-180");
+        assert_eq!(anchor_id("ภาษาไทย Rust"), "ภาษาไทย-rust");
+        assert_eq!(anchor_id("???"), "section");
+    }
+
+    #[test]
+    fn test_from_html_injects_ids() {
+        let html = "<p>Intro</p><h2>Setup &amp; Install</h2><p>x</p><H3 class=\"t\">Step <code>one</code></H3><h2>Setup &amp; Install</h2>";
+        let (out, toc) = from_html(html);
+
+        assert_eq!(
+            out,
+            "<p>Intro</p><h2 id=\"setup-install\">Setup &amp; Install</h2><p>x</p><H3 class=\"t\" id=\"step-one\">Step <code>one</code></H3><h2 id=\"setup-install-1\">Setup &amp; Install</h2>"
+        );
+        assert_eq!(toc.len(), 3);
+        assert_eq!((toc[0].level, toc[0].text.as_str(), toc[0].anchor.as_str()), (2, "Setup & Install", "setup-install"));
+        assert_eq!((toc[1].level, toc[1].anchor.as_str()), (3, "step-one"));
+        assert_eq!(toc[2].anchor, "setup-install-1");
+    }
+
+    #[test]
+    fn test_from_html_keeps_existing_ids() {
+        let (out, toc) = from_html("<h2 id='custom'>Title</h2><header>not a heading</header><hr>");
+        assert_eq!(out, "<h2 id='custom'>Title</h2><header>not a heading</header><hr>");
+        assert_eq!(toc.len(), 1);
+        assert_eq!(toc[0].anchor, "custom");
+    }
+
+    #[test]
+    fn test_from_html_without_headings() {
+        let (html, toc) = from_html("<p>No headings</p>");
+        assert_eq!(html, "<p>No headings</p>");
+        assert!(toc.is_empty());
+    }
+}
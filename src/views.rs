@@ -0,0 +1,288 @@
+use axum::http::HeaderMap;
+use chrono::NaiveDate;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::db::DbPool;
+
+/// How many days of per-visitor view rows are kept for popularity windows.
+pub const VIEW_RETENTION_DAYS: i64 = 90;
+
+/// Lowercase user agent fragments that identify crawlers, previews and scripts.
+const BOT_MARKERS: &[&str] = &[
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "mediapartners",
+    "facebookexternalhit",
+    "embedly",
+    "preview",
+    "headless",
+    "lighthouse",
+    "monitor",
+    "curl",
+    "wget",
+    "python-requests",
+    "httpclient",
+    "go-http-client",
+    "okhttp",
+    "axios",
+];
+
+/// Returns true when the user agent is missing or looks like an automated client.
+pub fn is_bot(user_agent: &str) -> bool {
+    let ua = user_agent.trim().to_lowercase();
+    ua.is_empty() || BOT_MARKERS.iter().any(|marker| ua.contains(marker))
+}
+
+/// Best-effort client IP from proxy headers; the app is deployed behind a reverse proxy.
+pub fn client_ip(headers: &HeaderMap) -> String {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .or_else(|| headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Anonymous visitor id. Includes the day so hashes can't be linked across days.
+pub fn visitor_hash(ip: &str, user_agent: &str, day: NaiveDate, salt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(b"|");
+    hasher.update(ip.as_bytes());
+    hasher.update(b"|");
+    hasher.update(user_agent.as_bytes());
+    hasher.update(b"|");
+    hasher.update(day.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+type PendingView = (i32, String, NaiveDate);
+
+/// Most views held between flushes. Past this (a flood of rotating user agents, or
+/// flushes failing) new views are dropped rather than growing memory without bound.
+const MAX_PENDING_VIEWS: usize = 100_000;
+
+/// `VIEW_HASH_SALT`, or a random salt for this process. Without a secret salt a
+/// visitor hash could be brute-forced back to the IP; a random one means a visitor
+/// seen before and after a restart on the same day may count twice.
+pub fn salt_from_env() -> String {
+    match std::env::var("VIEW_HASH_SALT") {
+        Ok(salt) if !salt.is_empty() => salt,
+        _ => {
+            tracing::warn!("VIEW_HASH_SALT is not set; using a random salt until restart");
+            let mut bytes = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            bytes.iter().map(|b| format!("{:02x}", b)).collect()
+        }
+    }
+}
+
+/// Buffers views in memory so a page view doesn't cost a database write.
+/// Pending views are written by `flush`, which `run_flusher` calls periodically.
+/// The latest day's flushed views are remembered too, so a repeat visit after a
+/// flush isn't reported as counted.
+#[derive(Clone)]
+pub struct ViewCounter {
+    pending: Arc<Mutex<HashSet<PendingView>>>,
+    flushed: Arc<Mutex<HashSet<PendingView>>>,
+    salt: Arc<str>,
+    max_pending: usize,
+}
+
+impl ViewCounter {
+    pub fn new(salt: String) -> Self {
+        Self::with_max_pending(salt, MAX_PENDING_VIEWS)
+    }
+
+    fn with_max_pending(salt: String, max_pending: usize) -> Self {
+        Self { pending: Arc::default(), flushed: Arc::default(), salt: salt.into(), max_pending }
+    }
+
+    /// `visitor_hash` with this counter's salt.
+    pub fn visitor(&self, ip: &str, user_agent: &str, day: NaiveDate) -> String {
+        visitor_hash(ip, user_agent, day, &self.salt)
+    }
+
+    /// Queue a view. Returns false if the same visitor is already queued or flushed for
+    /// this post today, or if the buffer is full and the view was dropped.
+    pub fn record(&self, post_id: i32, visitor: String, day: NaiveDate) -> bool {
+        let view = (post_id, visitor, day);
+        if self.flushed.lock().unwrap().contains(&view) {
+            return false;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if pending.len() >= self.max_pending {
+            return false;
+        }
+        pending.insert(view)
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Write queued views, skipping visitors already counted for that day, and bump
+    /// `blog_posts.view_count` by the number of new unique views.
+    pub async fn flush(&self, pool: &DbPool) -> Result<u64, sqlx::Error> {
+        let batch: Vec<PendingView> = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.drain().collect()
+        };
+
+        if batch.is_empty() {
+            return Ok(0);
+        }
+
+        let post_ids: Vec<i32> = batch.iter().map(|(id, _, _)| *id).collect();
+        let hashes: Vec<String> = batch.iter().map(|(_, hash, _)| hash.clone()).collect();
+        let days: Vec<NaiveDate> = batch.iter().map(|(_, _, day)| *day).collect();
+
+        let result = sqlx::query(
+            r#"
+WITH inserted AS (
+    INSERT INTO blog_post_views (post_id, visitor_hash, view_date)
+    SELECT v.post_id, v.visitor_hash, v.view_date
+    FROM UNNEST($1::int[], $2::text[], $3::date[]) AS v(post_id, visitor_hash, view_date)
+    JOIN blog_posts p ON p.id = v.post_id
+    ON CONFLICT DO NOTHING
+    RETURNING post_id
+)
+UPDATE blog_posts p
+SET view_count = COALESCE(p.view_count, 0) + i.views
+FROM (SELECT post_id, COUNT(*)::int AS views FROM inserted GROUP BY post_id) i
+WHERE p.id = i.post_id
+"#,
+        )
+        .bind(&post_ids)
+        .bind(&hashes)
+        .bind(&days)
+        .execute(pool)
+        .await;
+
+        match result {
+            Ok(res) => {
+                self.remember_flushed(batch);
+                Ok(res.rows_affected())
+            }
+            Err(e) => {
+                // Put the batch back so the next flush retries it, within the cap.
+                let mut pending = self.pending.lock().unwrap();
+                let room = self.max_pending.saturating_sub(pending.len());
+                pending.extend(batch.into_iter().take(room));
+                Err(e)
+            }
+        }
+    }
+
+    /// Keep the batch's views of its latest day, dropping earlier days, within the cap.
+    /// Past the cap a repeat visit reads as counted; the database still dedups it.
+    fn remember_flushed(&self, batch: Vec<PendingView>) {
+        let Some(latest) = batch.iter().map(|(_, _, day)| *day).max() else {
+            return;
+        };
+        let mut flushed = self.flushed.lock().unwrap();
+        flushed.retain(|(_, _, day)| *day >= latest);
+        let room = self.max_pending.saturating_sub(flushed.len());
+        flushed.extend(batch.into_iter().filter(|(_, _, day)| *day == latest).take(room));
+    }
+}
+
+/// Background task: flush buffered views every `every` and prune rows past retention.
+pub async fn run_flusher(counter: ViewCounter, pool: DbPool, every: Duration) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = counter.flush(&pool).await {
+            tracing::error!("Failed to flush blog views: {:?}", e);
+            continue;
+        }
+
+        if let Err(e) = sqlx::query("DELETE FROM blog_post_views WHERE view_date < CURRENT_DATE - $1::int")
+            .bind(VIEW_RETENTION_DAYS as i32)
+            .execute(&pool)
+            .await
+        {
+            tracing::error!("Failed to prune blog views: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, d).unwrap()
+    }
+
+    #[test]
+    fn test_is_bot_detects_crawlers() {
+        assert!(is_bot("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"));
+        assert!(is_bot("facebookexternalhit/1.1"));
+        assert!(is_bot("curl/8.4.0"));
+        assert!(is_bot(""));
+    }
+
+    #[test]
+    fn test_is_bot_allows_browsers() {
+        assert!(!is_bot("Mozilla/5.0 (Macintosh; Intel Mac OS X 14_0) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15"));
+        assert!(!is_bot("Mozilla/5.0 (X11; Linux x86_64; rv:120.0) Gecko/20100101 Firefox/120.0"));
+    }
+
+    #[test]
+    fn test_visitor_hash_changes_per_day() {
+        let a = visitor_hash("1.2.3.4", "Firefox", day(1), "salt");
+        let b = visitor_hash("1.2.3.4", "Firefox", day(1), "salt");
+        let c = visitor_hash("1.2.3.4", "Firefox", day(2), "salt");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_client_ip_prefers_first_forwarded_address() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        headers.insert("x-real-ip", "10.0.0.2".parse().unwrap());
+        assert_eq!(client_ip(&headers), "203.0.113.7");
+    }
+
+    #[test]
+    fn test_view_counter_dedups_pending_views() {
+        let counter = ViewCounter::new("salt".to_string());
+        assert!(counter.record(1, "abc".to_string(), day(1)));
+        assert!(!counter.record(1, "abc".to_string(), day(1)));
+        assert!(counter.record(1, "abc".to_string(), day(2)));
+        assert!(counter.record(2, "abc".to_string(), day(1)));
+        assert_eq!(counter.pending_len(), 3);
+    }
+
+    #[test]
+    fn test_view_counter_dedups_flushed_views() {
+        let counter = ViewCounter::with_max_pending("salt".to_string(), 2);
+        counter.remember_flushed(vec![(1, "a".to_string(), day(1))]);
+        assert!(!counter.record(1, "a".to_string(), day(1)));
+
+        // A later day's flush forgets the earlier one.
+        counter.remember_flushed(vec![(1, "b".to_string(), day(2)), (1, "c".to_string(), day(2)), (1, "d".to_string(), day(2))]);
+        assert!(counter.record(1, "a".to_string(), day(1)));
+        assert!(!counter.record(1, "b".to_string(), day(2)));
+        assert!(counter.record(1, "d".to_string(), day(2)));
+    }
+
+    #[test]
+    fn test_view_counter_drops_views_past_cap() {
+        let counter = ViewCounter::with_max_pending("salt".to_string(), 2);
+        assert!(counter.record(1, "a".to_string(), day(1)));
+        assert!(counter.record(1, "b".to_string(), day(1)));
+        assert!(!counter.record(1, "c".to_string(), day(1)));
+        assert_eq!(counter.pending_len(), 2);
+    }
+}
@@ -319,3 +319,166 @@ fn test_blog_post_published_false() {
 
     assert_eq!(payload["published"], false);
 }
+
+#[test]
+fn test_view_response() {
+    let response = serde_json::json!({
+        "success": true,
+        "counted": false
+    });
+
+    assert_eq!(response["success"], true);
+    assert_eq!(response["counted"], false);
+}
+
+#[test]
+fn test_popular_posts_response() {
+    let response = serde_json::json!([
+        {
+            "id": 2,
+            "title": "Post 2",
+            "slug": "post-2",
+            "excerpt": "Excerpt 2",
+            "featured_image": null,
+            "published_at": "2024-01-02T00:00:00",
+            "view_count": 120,
+            "window_views": 30
+        },
+        {
+            "id": 1,
+            "title": "Post 1",
+            "slug": "post-1",
+            "excerpt": "Excerpt 1",
+            "featured_image": null,
+            "published_at": "2024-01-01T00:00:00",
+            "view_count": 300,
+            "window_views": 12
+        }
+    ]);
+
+    assert_eq!(response.as_array().unwrap().len(), 2);
+    assert!(response[0]["window_views"].as_i64() >= response[1]["window_views"].as_i64());
+}
+
+#[test]
+fn test_blog_post_payload_scheduled() {
+    let payload = serde_json::json!({
+        "title": "Scheduled Post",
+        "excerpt": "Coming soon",
+        "content": "Scheduled content",
+        "published": true,
+        "published_at": "2099-01-01T09:00:00"
+    });
+
+    assert_eq!(payload["published"], true);
+    assert!(payload["published_at"].as_str().unwrap() > "2024-01-01T00:00:00");
+}
+
+#[test]
+fn test_preview_link_payload() {
+    let payload = serde_json::json!({
+        "expires_in_hours": 48
+    });
+
+    assert_eq!(payload["expires_in_hours"], 48);
+}
+
+#[test]
+fn test_preview_link_response() {
+    let response = serde_json::json!({
+        "token": "Ny4yLjE3MDAwMDAwMDA.c2lnbmF0dXJl",
+        "url": "/api/blog/posts/draft-post?preview=Ny4yLjE3MDAwMDAwMDA.c2lnbmF0dXJl",
+        "expires_at": "2024-01-04T00:00:00+00:00"
+    });
+
+    let url = response["url"].as_str().unwrap();
+    assert!(url.starts_with("/api/blog/posts/draft-post?preview="));
+    assert!(url.ends_with(response["token"].as_str().unwrap()));
+}
+
+#[test]
+fn test_comment_payload() {
+    let payload = serde_json::json!({
+        "name": "Reader",
+        "email": "reader@example.com",
+        "body": "Thanks for the *great* post",
+        "parent_id": 12
+    });
+
+    assert_eq!(payload["parent_id"], 12);
+    assert!(payload["email"].as_str().unwrap().contains('@'));
+}
+
+#[test]
+fn test_comment_submitted_response() {
+    let response = serde_json::json!({ "success": true, "id": 31, "status": "pending" });
+
+    assert_eq!(response["success"], true);
+    assert_eq!(response["status"], "pending");
+}
+
+#[test]
+fn test_translation_payload() {
+    let payload = serde_json::json!({
+        "fields": {
+            "title": "สวัสดีชาวโลก",
+            "content": "<p>บทความภาษาไทย</p>"
+        }
+    });
+
+    let fields = payload["fields"].as_object().unwrap();
+    assert_eq!(fields.len(), 2);
+    assert!(fields.values().all(|v| v.is_string()));
+}
+
+#[test]
+fn test_localized_post_response() {
+    let response = serde_json::json!({
+        "slug": "hello-world",
+        "title": "สวัสดีชาวโลก",
+        "locale": "th",
+        "available_locales": ["en", "th"]
+    });
+
+    assert_eq!(response["locale"], "th");
+    assert_eq!(response["available_locales"][0], "en");
+}
+
+#[test]
+fn test_tag_merge_response() {
+    let response = serde_json::json!({
+        "success": true,
+        "tag": { "id": 4, "name": "Rust", "slug": "rust" },
+        "moved_posts": 3
+    });
+
+    assert_eq!(response["tag"]["slug"], "rust");
+    assert_eq!(response["moved_posts"], 3);
+}
+
+#[test]
+fn test_bulk_tag_payload() {
+    let payload = serde_json::json!({ "add": [1, 2, 3], "remove": [9] });
+
+    assert_eq!(payload["add"].as_array().unwrap().len(), 3);
+    assert_eq!(payload["remove"][0], 9);
+}
+
+#[test]
+fn test_import_report() {
+    let report = serde_json::json!({
+        "dry_run": true,
+        "committed": false,
+        "files": [
+            { "file": "posts/hello.md", "slug": "hello", "action": "created", "post_id": null, "error": null, "changes": [{ "field": "title", "diff": "@@ -0,0 +1 @@\n+Hello\n" }] },
+            { "file": "posts/broken.md", "slug": null, "action": "error", "post_id": null, "error": "Front matter needs a `title`", "changes": [] }
+        ],
+        "created_categories": ["tutorials"],
+        "created_tags": []
+    });
+
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files[0]["action"], "created");
+    assert!(files[1]["error"].as_str().unwrap().contains("title"));
+    assert_eq!(report["committed"], false);
+}
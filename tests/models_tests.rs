@@ -10,6 +10,8 @@ fn test_about_serialization() {
         updated_at: None,
         categories: Some(serde_json::json!(["programming", "rust"])),
         tags: Some(serde_json::json!(["developer", "backend"])),
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&about).unwrap();
@@ -46,6 +48,8 @@ fn test_skill_serialization() {
         updated_at: None,
         categories: Some(serde_json::json!(["programming"])),
         tags: Some(serde_json::json!(["language"])),
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&skill).unwrap();
@@ -84,6 +88,8 @@ fn test_experience_timeline_serialization() {
         categories: None,
         tags: None,
         details: None,
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&experience).unwrap();
@@ -105,6 +111,8 @@ fn test_experience_timeline_with_details() {
         categories: Some(serde_json::json!(["engineering"])),
         tags: Some(serde_json::json!(["backend", "senior"])),
         details: Some(serde_json::json!(["Led team of 5 developers", "Implemented CI/CD"])),
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&experience).unwrap();
@@ -119,21 +127,36 @@ fn test_experience_timeline_with_details() {
 fn test_project_serialization() {
     let project = Project {
         id: 1,
+        slug: "awesome-project".to_string(),
         title: "Awesome Project".to_string(),
         description: "A great project".to_string(),
         image: "https://example.com/image.png".to_string(),
         skills: Some(vec!["Rust".to_string(), "Axum".to_string()]),
         link: "https://github.com/user/project".to_string(),
         is_active: Some(true),
+        role: Some("Lead developer".to_string()),
+        team_size: Some(3),
+        start_date: chrono::NaiveDate::from_ymd_opt(2024, 1, 15),
+        end_date: None,
+        case_study: Some("## Problem\n\nSlow pages.".to_string()),
+        case_study_html: None,
         created_at: None,
         updated_at: None,
         categories: None,
         tags: None,
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&project).unwrap();
     assert!(json.contains("Awesome Project"));
     assert!(json.contains("https://github.com"));
+    assert!(json.contains("\"start_date\":\"2024-01-15\""));
+
+    let summary = serde_json::to_value(ProjectSummary::from(project)).unwrap();
+    assert_eq!(summary["slug"], "awesome-project");
+    assert_eq!(summary["has_case_study"], true);
+    assert!(summary.get("case_study").is_none());
 }
 
 #[test]
@@ -199,6 +222,7 @@ fn test_blog_category_serialization() {
         updated_at: None,
         categories: None,
         tags: None,
+        post_count: None,
     };
 
     let json = serde_json::to_string(&category).unwrap();
@@ -216,6 +240,7 @@ fn test_blog_tag_serialization() {
         updated_at: None,
         categories: None,
         tags: None,
+        post_count: None,
     };
 
     let json = serde_json::to_string(&tag).unwrap();
@@ -245,6 +270,14 @@ fn test_blog_post_serialization() {
         updated_at: None,
         categories: None,
         tags: None,
+        authors: None,
+        series: None,
+        previous_post: None,
+        next_post: None,
+        comments_closed: None,
+        toc: None,
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&post).unwrap();
@@ -252,6 +285,24 @@ fn test_blog_post_serialization() {
     assert!(json.contains("hello-world"));
 }
 
+#[test]
+fn test_blog_post_neighbours_deserialize() {
+    let json = r#"{
+        "id": 2, "uuid": null, "title": "B", "slug": "b", "excerpt": "", "content": "",
+        "content_markdown": null, "featured_image": null, "author": null, "published": true,
+        "published_at": "2024-02-01T00:00:00", "view_count": 0, "reading_time": 1,
+        "meta_title": null, "meta_description": null, "meta_keywords": null,
+        "created_at": null, "updated_at": null,
+        "previous_post": {"id": 1, "title": "A", "slug": "a", "published_at": "2024-01-01T00:00:00"},
+        "next_post": null
+    }"#;
+
+    let post: BlogPost = serde_json::from_str(json).unwrap();
+    assert_eq!(post.previous_post.unwrap()["slug"], "a");
+    assert!(post.next_post.is_none());
+    assert!(post.series.is_none());
+}
+
 #[test]
 fn test_skill_with_optional_fields() {
     let skill = Skill {
@@ -263,6 +314,8 @@ fn test_skill_with_optional_fields() {
         updated_at: None,
         categories: None,
         tags: None,
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&skill).unwrap();
@@ -277,16 +330,25 @@ fn test_skill_with_optional_fields() {
 fn test_project_with_defaults() {
     let project = Project {
         id: 1,
+        slug: "test-project".to_string(),
         title: "Test Project".to_string(),
         description: "Description".to_string(),
         image: "image.png".to_string(),
         skills: None,
         link: "https://example.com".to_string(),
         is_active: None,
+        role: None,
+        team_size: None,
+        start_date: None,
+        end_date: None,
+        case_study: None,
+        case_study_html: None,
         created_at: None,
         updated_at: None,
         categories: None,
         tags: None,
+        locale: None,
+        available_locales: None,
     };
 
     let json = serde_json::to_string(&project).unwrap();
@@ -294,3 +356,239 @@ fn test_project_with_defaults() {
     assert!(parsed.is_active.is_none());
     assert!(parsed.skills.is_none());
 }
+
+#[test]
+fn test_popular_post_serialization() {
+    let post = PopularPost {
+        id: 1,
+        title: "Hello World".to_string(),
+        slug: "hello-world".to_string(),
+        excerpt: "Excerpt".to_string(),
+        featured_image: None,
+        published_at: None,
+        view_count: Some(250),
+        window_views: 42,
+    };
+
+    let json = serde_json::to_value(&post).unwrap();
+    assert_eq!(json["slug"], "hello-world");
+    assert_eq!(json["view_count"], 250);
+    assert_eq!(json["window_views"], 42);
+}
+
+#[test]
+fn test_blog_post_revision_serialization() {
+    let revision = BlogPostRevision {
+        id: 3,
+        post_id: 1,
+        title: "Old Title".to_string(),
+        excerpt: "Old excerpt".to_string(),
+        content: "Old content".to_string(),
+        content_markdown: Some("# Old".to_string()),
+        author: Some("Pasu".to_string()),
+        meta_title: None,
+        meta_description: None,
+        meta_keywords: Some(vec!["rust".to_string()]),
+        category_ids: vec![1, 2],
+        tag_ids: vec![],
+        author_ids: vec![],
+        created_at: None,
+    };
+
+    let json = serde_json::to_value(&revision).unwrap();
+    assert_eq!(json["post_id"], 1);
+    assert_eq!(json["title"], "Old Title");
+    assert_eq!(json["category_ids"], serde_json::json!([1, 2]));
+    assert_eq!(json["tag_ids"], serde_json::json!([]));
+}
+
+#[test]
+fn test_related_post_serialization() {
+    let post = RelatedPost {
+        id: 2,
+        title: "Axum Middleware".to_string(),
+        slug: "axum-middleware".to_string(),
+        excerpt: "Excerpt".to_string(),
+        featured_image: Some("https://example.com/axum.jpg".to_string()),
+        published_at: None,
+        shared_tags: 2,
+        shared_categories: 1,
+        score: 5.0,
+    };
+
+    let json = serde_json::to_value(&post).unwrap();
+    assert_eq!(json["slug"], "axum-middleware");
+    assert_eq!(json["shared_tags"], 2);
+    assert_eq!(json["shared_categories"], 1);
+    assert_eq!(json["score"], 5.0);
+}
+
+#[test]
+fn test_blog_series_serialization() {
+    let series = BlogSeries {
+        id: 1,
+        title: "Rust From Zero".to_string(),
+        slug: "rust-from-zero".to_string(),
+        description: Some("A beginner series".to_string()),
+        created_at: None,
+        updated_at: None,
+        post_count: Some(3),
+    };
+
+    let json = serde_json::to_value(&series).unwrap();
+    assert_eq!(json["slug"], "rust-from-zero");
+    assert_eq!(json["post_count"], 3);
+
+    let part = SeriesPost {
+        id: 7,
+        title: "Part 2: Ownership".to_string(),
+        slug: "ownership".to_string(),
+        excerpt: "Excerpt".to_string(),
+        featured_image: None,
+        published_at: None,
+        position: 2,
+    };
+    assert_eq!(serde_json::to_value(&part).unwrap()["position"], 2);
+}
+
+#[test]
+fn test_comment_node_serialization() {
+    let reply = CommentNode {
+        id: 2,
+        parent_id: Some(1),
+        author_name: "Reader".to_string(),
+        body_html: "<p>Agreed</p>".to_string(),
+        created_at: None,
+        replies: vec![],
+    };
+    let comment = CommentNode {
+        id: 1,
+        parent_id: None,
+        author_name: "Pasu".to_string(),
+        body_html: "<p>Hello</p>".to_string(),
+        created_at: None,
+        replies: vec![reply],
+    };
+
+    let json = serde_json::to_value(&comment).unwrap();
+    assert_eq!(json["replies"][0]["id"], 2);
+    assert_eq!(json["replies"][0]["parent_id"], 1);
+    assert!(json.get("author_email").is_none());
+}
+
+#[test]
+fn test_blog_comment_deserialization_defaults() {
+    let json = r#"{
+        "id": 5,
+        "post_id": 1,
+        "parent_id": null,
+        "author_name": "Reader",
+        "author_email": "reader@example.com",
+        "body": "**Nice**",
+        "body_html": "<p><strong>Nice</strong></p>",
+        "status": "pending",
+        "created_at": null,
+        "updated_at": null
+    }"#;
+
+    let comment: BlogComment = serde_json::from_str(json).unwrap();
+    assert_eq!(comment.status, "pending");
+    assert!(comment.post_slug.is_none());
+}
+
+#[test]
+fn test_toc_entry_serialization() {
+    let entry = TocEntry {
+        level: 2,
+        text: "Setup & Install".to_string(),
+        anchor: "setup-install".to_string(),
+    };
+
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["level"], 2);
+    assert_eq!(json["anchor"], "setup-install");
+}
+
+#[test]
+fn test_translation_deserialization() {
+    let json = r#"{
+        "locale": "th",
+        "fields": { "title": "สวัสดี", "excerpt": "บทความแรก" },
+        "updated_at": null
+    }"#;
+
+    let translation: Translation = serde_json::from_str(json).unwrap();
+    assert_eq!(translation.locale, "th");
+    assert_eq!(translation.fields["title"], "สวัสดี");
+}
+
+#[test]
+fn test_about_without_locale_fields() {
+    let json = r#"{
+        "id": 1,
+        "user_bio": "Hello",
+        "user_bio2": "World",
+        "created_at": null,
+        "updated_at": null
+    }"#;
+
+    let about: About = serde_json::from_str(json).unwrap();
+    assert!(about.locale.is_none());
+    assert!(about.available_locales.is_none());
+}
+
+#[test]
+fn test_blog_tag_post_count() {
+    let json = r#"{
+        "id": 2,
+        "name": "Rust",
+        "slug": "rust",
+        "created_at": null,
+        "updated_at": null,
+        "post_count": 7
+    }"#;
+
+    let tag: BlogTag = serde_json::from_str(json).unwrap();
+    assert_eq!(tag.post_count, Some(7));
+
+    let bare: BlogTag = serde_json::from_str(r#"{"id": 3, "name": "Go", "slug": "go", "created_at": null, "updated_at": null}"#).unwrap();
+    assert!(bare.post_count.is_none());
+}
+
+#[test]
+fn test_archive_year_serialization() {
+    let year = ArchiveYear {
+        year: 2024,
+        post_count: 5,
+        months: vec![
+            ArchiveMonth { year: 2024, month: 12, post_count: 3 },
+            ArchiveMonth { year: 2024, month: 6, post_count: 2 },
+        ],
+    };
+
+    let json = serde_json::to_value(&year).unwrap();
+    assert_eq!(json["post_count"], 5);
+    assert_eq!(json["months"][0]["month"], 12);
+}
+
+#[test]
+fn test_blog_author_serialization() {
+    let author = BlogAuthor {
+        id: 1,
+        name: "Jane Doe".to_string(),
+        slug: "jane-doe".to_string(),
+        bio: None,
+        avatar: Some("https://example.com/jane.png".to_string()),
+        social_links: Some(serde_json::json!({ "github": "https://github.com/jane" })),
+        created_at: None,
+        updated_at: None,
+        post_count: Some(3),
+    };
+
+    let json = serde_json::to_value(&author).unwrap();
+    assert_eq!(json["social_links"]["github"], "https://github.com/jane");
+    assert_eq!(json["post_count"], 3);
+
+    let parsed: BlogAuthor = serde_json::from_str(r#"{"id": 1, "name": "Jane", "slug": "jane", "bio": null, "avatar": null, "social_links": {}, "created_at": null, "updated_at": null}"#).unwrap();
+    assert!(parsed.post_count.is_none());
+}